@@ -0,0 +1,72 @@
+//! Collects all scaffolded solutions in `src/bin` into a registry for the main binary.
+//! Solutions are only compiled in with the `solutions` feature, so setup commands keep working while a day doesn't compile.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let is_enabled = env::var_os("CARGO_FEATURE_SOLUTIONS").is_some();
+
+    // without the feature, the registry is empty and the solutions are not compiled at all.
+    let mut days = if is_enabled {
+        find_days(&bin_dir)
+    } else {
+        vec![]
+    };
+
+    days.sort();
+
+    // lints of solutions are already reported when building their own binaries.
+    let mut out = String::new();
+
+    for (year, day, path) in &days {
+        out.push_str(&format!(
+            "#[cfg(not(test))]\n#[path = {path:?}]\n#[allow(warnings, clippy::all)]\nmod year_{year}_day_{day:02};\n"
+        ));
+    }
+
+    let entries = days
+        .iter()
+        .map(|(year, day, _)| format!("year_{year}_day_{day:02}::SOLUTION"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    // solutions are not compiled into the test harness of the main binary, their tests already run as part of their own binary.
+    out.push_str(&format!(
+        "#[cfg(not(test))]\npub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[{entries}];\n"
+    ));
+    out.push_str(
+        "#[cfg(test)]\npub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[];\n",
+    );
+
+    fs::write(
+        Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs"),
+        out,
+    )
+    .unwrap();
+}
+
+/// Finds the solutions in `bin_dir`, named `{year}_{day}.rs`, e.g. `2023_01.rs`.
+fn find_days(bin_dir: &Path) -> Vec<(u16, u8, String)> {
+    fs::read_dir(bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    let (year, day) = path.file_stem()?.to_str()?.split_once('_')?;
+                    let year = year.parse::<u16>().ok()?;
+                    let day = day
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|day| (1..=25).contains(day))?;
+                    let contents = fs::read_to_string(&path).ok()?;
+                    contents
+                        .contains("solution!(")
+                        .then(|| (year, day, path.display().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
@@ -1,4 +1,4 @@
-advent_of_code::solution!(1);
+advent_of_code::solution!(2023, 1);
 
 fn replace_numbers(str: &str) -> String {
     let mut replace = String::from(str);
@@ -52,15 +52,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let str = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
-        let result = part_one(str);
-        // let result = part_one(&advent_of_code::template::read_file("examples", DAY));    // not valid because the example input changed
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(142));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", PUZZLE, 2,
+        ));
         assert_eq!(result, Some(281));
     }
 }
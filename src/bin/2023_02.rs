@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(2);
+use advent_of_code::template::error::{parse_lines, InputError};
+
+advent_of_code::solution!(2023, 2);
 
 const BLUE_THRESHOLD: i32 = 14;
 const RED_THRESHOLD: i32 = 12;
@@ -38,61 +40,62 @@ struct Set {
     possible: bool,
 }
 
-fn map_set(str: &str) -> Set {
+fn map_set(str: &str) -> Result<Set, String> {
     let mut colors = HashMap::new();
-    str.split(", ").for_each(|s| {
-        let vec = s.trim().split(' ').collect::<Vec<_>>();
-        let n = vec[0].parse::<i32>().unwrap();
-        let c = vec[1];
+    for s in str.split(", ") {
+        let (n, c) = s
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| format!("expected `<count> <color>`, got `{}`", s.trim()))?;
+        let n = n
+            .parse::<i32>()
+            .map_err(|e| format!("invalid count `{n}`: {e}"))?;
         colors.insert(c, n);
-    }); // blue = s.split(" ").collect::<Vec<_>>()[0].parse::<i32>().unwrap()
+    }
     let blue = *colors.get("blue").unwrap_or(&0);
     let red = *colors.get("red").unwrap_or(&0);
     let green = *colors.get("green").unwrap_or(&0);
     let possible = blue <= BLUE_THRESHOLD && red <= RED_THRESHOLD && green <= GREEN_THRESHOLD;
-    Set {
+    Ok(Set {
         blue,
         red,
         green,
         possible,
-    }
+    })
 }
 
-fn map_games(str: &str) -> Game {
-    let splits: Vec<_> = str.split(':').collect::<Vec<_>>();
-    let id = splits[0]
-        .split(' ')
-        .collect::<Vec<_>>()
-        .last()
-        .unwrap()
+fn map_games(str: &str) -> Result<Game, String> {
+    let (game, sets) = str.split_once(':').ok_or("expected `Game <id>: <sets>`")?;
+    let id = game
+        .rsplit(' ')
+        .next()
+        .ok_or("expected `Game <id>`")?
         .parse::<i32>()
-        .unwrap();
-    let sets: &Vec<_> = &splits[1].split(';').map(map_set).collect();
-    Game {
-        id,
-        sets: sets.to_vec(),
-    }
+        .map_err(|e| format!("invalid game id: {e}"))?;
+    let sets = sets
+        .split(';')
+        .map(map_set)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Game { id, sets })
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .map(map_games)
-            .filter(Game::is_possible)
+pub fn part_one(input: &str) -> Result<Option<u32>, InputError> {
+    Ok(Some(
+        parse_lines(input, map_games)?
+            .iter()
+            .filter(|g| g.is_possible())
             .map(|g| g.id as u32)
             .sum::<u32>(),
-    )
+    ))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .map(map_games)
+pub fn part_two(input: &str) -> Result<Option<u32>, InputError> {
+    Ok(Some(
+        parse_lines(input, map_games)?
+            .iter()
             .map(|g| g.get_green() * g.get_red() * g.get_blue())
             .sum(),
-    )
+    ))
 }
 
 #[cfg(test)]
@@ -101,13 +104,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result.unwrap(), Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2286));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result.unwrap(), Some(2286));
     }
 }
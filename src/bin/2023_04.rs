@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use advent_of_code::template::error::{parse_lines, InputError};
+
+advent_of_code::solution!(2023, 4, parse);
+
+#[derive(Debug)]
+pub struct Card {
+    id: i32,
+    winning_numbers: Vec<i32>,
+    card_numbers: Vec<i32>,
+}
+
+impl Card {
+    fn get_score(&self) -> u32 {
+        let wins = self.get_winning_numbers();
+        if wins == 0 {
+            return 0;
+        }
+        2_u32.pow(wins - 1)
+    }
+
+    fn get_winning_numbers(&self) -> u32 {
+        self.card_numbers
+            .iter()
+            .filter(|n| self.winning_numbers.contains(n))
+            .count() as u32
+    }
+}
+
+fn parse_numbers(numbers: &str) -> Result<Vec<i32>, String> {
+    numbers
+        .split_whitespace()
+        .map(|n| {
+            n.parse::<i32>()
+                .map_err(|e| format!("invalid number `{n}`: {e}"))
+        })
+        .collect()
+}
+
+fn parse_card(line: &str) -> Result<Card, String> {
+    let (card, numbers) = line
+        .split_once(": ")
+        .ok_or("expected `Card <id>: <winning numbers> | <numbers>`")?;
+    let id = card
+        .split_whitespace()
+        .last()
+        .ok_or("expected `Card <id>`")?
+        .parse::<i32>()
+        .map_err(|e| format!("invalid card id: {e}"))?;
+    let (winning_numbers, card_numbers) = numbers
+        .split_once(" | ")
+        .ok_or("expected `<winning numbers> | <numbers>`")?;
+    Ok(Card {
+        id,
+        winning_numbers: parse_numbers(winning_numbers)?,
+        card_numbers: parse_numbers(card_numbers)?,
+    })
+}
+
+pub fn parse(input: &str) -> Result<Vec<Card>, InputError> {
+    parse_lines(input, parse_card)
+}
+
+pub fn part_one(cards: &[Card]) -> Option<u32> {
+    Some(cards.iter().map(|c| c.get_score()).sum::<u32>())
+}
+
+pub fn part_two(cards: &[Card]) -> Option<u32> {
+    let mut won_card = HashMap::new();
+    let splits = cards
+        .iter()
+        .map(|card| {
+            let num_of_copy = 1 + won_card.get(&card.id).unwrap_or(&0);
+            let num_winned = card.get_winning_numbers() as i32;
+            for i in card.id + 1..card.id + 1 + num_winned {
+                let current_value = won_card.get(&i).unwrap_or(&0);
+                won_card.insert(i, current_value + num_of_copy);
+            }
+            num_of_copy as u32
+        })
+        .sum::<u32>();
+    Some(splits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result =
+            part_one(&parse(&advent_of_code::template::read_file("examples", PUZZLE)).unwrap());
+        assert_eq!(result, Some(13));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result =
+            part_two(&parse(&advent_of_code::template::read_file("examples", PUZZLE)).unwrap());
+        assert_eq!(result, Some(30));
+    }
+
+    #[test]
+    fn test_parse_malformed_card() {
+        assert!(parse(": 1 2 | 3 4").is_err());
+    }
+}
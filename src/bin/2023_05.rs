@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use advent_of_code::template::error::InputError;
+
+advent_of_code::solution!(2023, 5, parse);
+
+type RangeMap = Range<u64>;
+
+/// The maps that lead from seeds to locations, in order.
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+#[derive(Debug)]
+struct Convertor {
+    input_range: Range<u64>,
+    output_range: u64,
+}
+
+impl Convertor {
+    fn get(&self, source: u64) -> Option<u64> {
+        if self.input_range.contains(&source) {
+            let value = self.output_range + source - self.input_range.start;
+            return Some(value);
+        }
+        None
+    }
+
+    fn get_convertor(map: &str) -> Result<Convertor, String> {
+        let split = map
+            .split_whitespace()
+            .map(|n| {
+                n.parse::<u64>()
+                    .map_err(|e| format!("invalid number `{n}`: {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let [target_start, source_start, length] = split[..] else {
+            return Err(format!("expected 3 numbers, got {}", split.len()));
+        };
+        Ok(Convertor {
+            input_range: source_start..source_start + length,
+            output_range: target_start,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ConvertorMap {
+    convertors: Vec<Convertor>,
+}
+
+impl ConvertorMap {
+    fn get(&self, source: u64) -> Option<u64> {
+        let convertor = self
+            .convertors
+            .iter()
+            .find(|c| c.input_range.contains(&source));
+        if let Some(convertor) = convertor {
+            return convertor.get(source);
+        }
+        Some(source)
+    }
+
+    /// Parses a map that starts on line `line` of the input.
+    fn get_convertors(map: &str, line: usize) -> Result<(&str, ConvertorMap), InputError> {
+        let mut convertors = vec![];
+        let (raw_name, values) = map.split_once('\n').unwrap_or((map, ""));
+        let (name, _) = raw_name
+            .split_once(' ')
+            .ok_or_else(|| InputError::new(line, "expected `<name> map:`").with_text(raw_name))?;
+        for (i, l) in values.lines().enumerate() {
+            let convertor = Convertor::get_convertor(l).map_err(|e| {
+                InputError::new(line + i + 1, "invalid range")
+                    .with_text(l)
+                    .with_source(e)
+            })?;
+            convertors.push(convertor);
+        }
+        Ok((name, ConvertorMap { convertors }))
+    }
+
+    fn translate(&self, ranges: Vec<RangeMap>) -> Vec<RangeMap> {
+        let mut output = vec![];
+
+        ranges.iter().for_each(|input_range| {
+            let mut output_ranges: Vec<RangeMap> = vec![];
+            let mut covered_ranges: Vec<RangeMap> = vec![];
+            let mut uncovered_ranges: Vec<RangeMap> = vec![];
+
+            for convertor in &self.convertors {
+                if convertor.input_range.start < input_range.end
+                    && convertor.input_range.end > input_range.start
+                {
+                    let start_range = convertor.input_range.start.max(input_range.start);
+                    let end_range = convertor.input_range.end.min(input_range.end);
+                    let total_items = (start_range..end_range).count() as u64;
+
+                    let mut gap = 0;
+                    if start_range > convertor.input_range.start {
+                        gap = start_range - convertor.input_range.start;
+                    }
+
+                    covered_ranges.push(start_range..end_range);
+                    output_ranges.push(RangeMap {
+                        start: convertor.output_range + gap,
+                        end: convertor.output_range + gap + total_items,
+                    });
+                }
+            }
+
+            if covered_ranges.is_empty() {
+                output.push(input_range.to_owned());
+            } else {
+                covered_ranges.sort_by_key(|r| r.start);
+                let mut current_range = &covered_ranges[0];
+                if input_range.start != current_range.start {
+                    uncovered_ranges.push(RangeMap {
+                        start: input_range.start,
+                        end: current_range.end - 1,
+                    });
+                }
+
+                for next_range in covered_ranges.iter().skip(1) {
+                    if current_range.end < next_range.start {
+                        output_ranges.push(RangeMap {
+                            start: current_range.end,
+                            end: next_range.start,
+                        });
+                        current_range = next_range;
+                    }
+                }
+
+                if current_range.end < input_range.end {
+                    output_ranges.push(RangeMap {
+                        start: current_range.end,
+                        end: input_range.end,
+                    });
+                }
+            }
+
+            output.extend(output_ranges);
+            output.extend(uncovered_ranges);
+        });
+
+        output
+    }
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<u64>,
+    convertors_map: HashMap<String, ConvertorMap>,
+}
+
+impl Almanac {
+    fn get(&self, name: &str) -> &ConvertorMap {
+        // every map of `MAPS` is checked to be present while parsing.
+        &self.convertors_map[name]
+    }
+}
+
+fn get_seeds(seeds: &[u64]) -> Vec<RangeMap> {
+    seeds
+        .chunks_exact(2)
+        .map(|pair| pair[0]..pair[0] + pair[1])
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<Almanac, InputError> {
+    let (raw_init, maps) = input.split_once("\n\n").unwrap_or((input, ""));
+    let (_, init) = raw_init
+        .split_once(':')
+        .ok_or_else(|| InputError::new(1, "expected `seeds: <numbers>`").with_text(raw_init))?;
+    let seeds = init
+        .split_whitespace()
+        .map(|s| s.parse::<u64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            InputError::new(1, "invalid seed")
+                .with_text(raw_init)
+                .with_source(e)
+        })?;
+
+    let mut convertors_map = HashMap::new();
+    // maps start after the seeds and an empty line, and are separated by empty lines.
+    let mut line = raw_init.lines().count() + 2;
+    for m in maps.split("\n\n").filter(|m| !m.trim().is_empty()) {
+        let (name, map) = ConvertorMap::get_convertors(m, line)?;
+        convertors_map.insert(name.to_string(), map);
+        line += m.lines().count() + 1;
+    }
+
+    if let Some(missing) = MAPS
+        .iter()
+        .find(|name| !convertors_map.contains_key(**name))
+    {
+        let last_line = input.lines().count().max(1);
+        return Err(InputError::new(
+            last_line,
+            format!("missing `{missing} map`"),
+        ));
+    }
+
+    Ok(Almanac {
+        seeds,
+        convertors_map,
+    })
+}
+
+pub fn part_one(almanac: &Almanac) -> Option<u32> {
+    almanac
+        .seeds
+        .iter()
+        .map(|&seed| almanac.get("seed-to-soil").get(seed).unwrap())
+        .map(|seed| almanac.get("soil-to-fertilizer").get(seed).unwrap())
+        .map(|seed| almanac.get("fertilizer-to-water").get(seed).unwrap())
+        .map(|seed| almanac.get("water-to-light").get(seed).unwrap())
+        .map(|seed| almanac.get("light-to-temperature").get(seed).unwrap())
+        .map(|seed| almanac.get("temperature-to-humidity").get(seed).unwrap())
+        .map(|seed| almanac.get("humidity-to-location").get(seed).unwrap())
+        .map(|r| r as u32)
+        .min()
+}
+
+pub fn part_two(almanac: &Almanac) -> Option<u32> {
+    let seeds = get_seeds(&almanac.seeds);
+
+    let soil = almanac.get("seed-to-soil").translate(seeds);
+    let fert = almanac.get("soil-to-fertilizer").translate(soil);
+    let water = almanac.get("fertilizer-to-water").translate(fert);
+    let light = almanac.get("water-to-light").translate(water);
+    let temp = almanac.get("light-to-temperature").translate(light);
+    let hum = almanac.get("temperature-to-humidity").translate(temp);
+    let location = almanac.get("humidity-to-location").translate(hum);
+    location.iter().map(|r| r.start as u32).min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result =
+            part_one(&parse(&advent_of_code::template::read_file("examples", PUZZLE)).unwrap());
+        assert_eq!(result, Some(35));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result =
+            part_two(&parse(&advent_of_code::template::read_file("examples", PUZZLE)).unwrap());
+        assert_eq!(result, Some(46));
+    }
+
+    #[test]
+    fn test_convertor() {
+        let convertor = Convertor {
+            output_range: 10,
+            input_range: 1..9,
+        };
+        let result = convertor.get(2);
+        assert_eq!(result, Some(11))
+    }
+}
@@ -1,4 +1,4 @@
-advent_of_code::solution!(6);
+advent_of_code::solution!(2023, 6);
 
 #[derive(PartialEq, Debug)]
 struct Race {
@@ -60,19 +60,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(288));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(71503));
     }
 
     #[test]
     fn test_get_races() {
-        let result = get_races(&advent_of_code::template::read_file("examples", DAY));
+        let result = get_races(&advent_of_code::template::read_file("examples", PUZZLE));
         let expected = vec![
             Race {
                 time: 7f64,
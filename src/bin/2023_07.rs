@@ -1,9 +1,9 @@
 use itertools::Itertools;
 
-use crate::Card::*;
-use crate::HandType::*;
+use self::Card::*;
+use self::HandType::*;
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(2023, 7);
 
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq)]
 enum HandType {
@@ -148,13 +148,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6440));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(5905));
     }
 }
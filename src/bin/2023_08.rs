@@ -3,9 +3,9 @@ use std::collections::HashMap;
 use num::integer::lcm;
 use regex::Regex;
 
-use crate::Direction::{Left, Right};
+use self::Direction::{Left, Right};
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(2023, 8);
 
 type Map = HashMap<String, Node>;
 
@@ -121,24 +121,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6));
     }
 
     #[test]
     fn test_part_two() {
-        let input = "LR
-
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)
-";
-        let result = part_two(input);
+        let result = part_two(&advent_of_code::template::read_file_part("examples", PUZZLE, 2));
         assert_eq!(result, Some(6));
     }
 }
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(2023, 9);
 
 fn parse_history(str: &str) -> Vec<i64> {
     str.split_whitespace().map(|n| n.parse::<i64>().unwrap()).collect_vec()
@@ -46,13 +46,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(114));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2));
     }
 }
@@ -1,13 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use advent_of_code::template::visualize::{self, Frame, Rgb};
 use itertools::Itertools;
-use colored::Colorize;
 use regex::Regex;
 
-use crate::CellType::{Ground, Pipe, Start};
-use crate::Direction::{East, North, South, West};
+use self::CellType::{Ground, Pipe, Start};
+use self::Direction::{East, North, South, West};
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(2023, 10);
 
 type Grid = Vec<Vec<Cell>>;
 
@@ -35,7 +35,7 @@ impl Maze {
 
         Maze { grid, start }
     }
-    fn get_next_cells(&self, cell: (usize, usize)) -> HashMap<crate::Direction, &crate::Cell> {
+    fn get_next_cells(&self, cell: (usize, usize)) -> HashMap<Direction, &Cell> {
         self.get_neighbours(&self.grid[cell.0][cell.1])
     }
 
@@ -191,34 +191,43 @@ pub fn part_two(input: &str) -> Option<u32> {
             }
         }
     }
-    // print_maze(input, steps, inner_cells.clone());
+    visualize::frame(|| maze_frame(input, &steps, &inner_cells));
     Some(inner_cells.len() as u32)
 }
 
+/// The maze with the loop in yellow and the cells it encloses in green.
+fn maze_frame(input: &str, steps: &[(usize, usize)], inner_cells: &[(usize, usize)]) -> Frame {
+    let path: HashSet<_> = steps.iter().collect();
+    let inner: HashSet<_> = inner_cells.iter().collect();
+    let width = input.lines().map(str::len).max().unwrap_or(0);
+
+    Frame::colors(width, input.lines().count(), |x, y| {
+        if path.contains(&(y, x)) {
+            Rgb(230, 190, 40)
+        } else if inner.contains(&(y, x)) {
+            Rgb(60, 180, 75)
+        } else {
+            Rgb(30, 30, 30)
+        }
+    })
+    .with_caption(format!("{} enclosed cells", inner_cells.len()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let input = ".F----7F7F7F7F-7....
-.|F--7||||||||FJ....
-.||.FJ||||||||L7....
-FJL7L7LJLJ||LJ.L-7..
-L--J.L7...LJS7F-7L7.
-....F-J..F7FJ|L7L7L7
-....L7.F7||L7|.L7L7|
-.....|FJLJ|FJ|F7|.LJ
-....FJL-7.||.||||...
-....L---J.LJ.LJLJ...
-";
-        let result = part_two(input);
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", PUZZLE, 2,
+        ));
         assert_eq!(result, Some(8));
     }
 }
@@ -2,7 +2,7 @@ use std::ops::RangeInclusive;
 
 use itertools::Itertools;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(2023, 11);
 
 fn range(a: usize, b: usize) -> RangeInclusive<usize> {
     if a < b {
@@ -89,7 +89,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(374));
     }
 
@@ -111,6 +111,6 @@ mod tests {
     }
 
     fn test_part_two(expansion: i32, expected: usize) {
-        assert_eq!(solve(&advent_of_code::template::read_file("examples", DAY), expansion), expected);
+        assert_eq!(solve(&advent_of_code::template::read_file("examples", PUZZLE), expansion), expected);
     }
 }
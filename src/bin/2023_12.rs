@@ -3,9 +3,9 @@ use std::collections::HashMap;
 use itertools::Itertools;
 use rayon::prelude::*;
 
-use crate::Spring::{Damaged, Operational, Unknown};
+use self::Spring::{Damaged, Operational, Unknown};
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(2023, 12);
 
 #[derive(Eq, Hash, PartialEq, Clone)]
 enum Spring {
@@ -167,13 +167,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(21));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(525152));
     }
 }
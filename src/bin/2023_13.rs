@@ -1,7 +1,7 @@
 use colored::Colorize;
 use itertools::Itertools;
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(2023, 13);
 
 fn reflects_at(input: &Vec<Vec<char>>, expected_diff: usize) -> Option<usize> {
     (1..input.len()).find(|&offset| {
@@ -51,13 +51,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(405));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(400));
     }
 }
@@ -1,8 +1,9 @@
+use advent_of_code::template::visualize::{self, Frame};
 use itertools::Itertools;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(2023, 14);
 
 type Grid = Vec<Vec<char>>;
 
@@ -54,11 +55,10 @@ fn rotate(grid: &Grid) -> Grid {
     new_grid
 }
 
-fn print_grid(grid: Grid) {
-    let result = grid.iter().map(|l| l.iter().collect::<String>())
-        .join("\n");
-    println!("==============\n{}", result);
-    println!("current weight = {}", get_weight(&grid));
+/// The grid turned back with north up, as in the input.
+fn grid_frame(grid: &Grid, caption: &str) -> Frame {
+    let north_up = rotate(&rotate(&rotate(grid)));
+    Frame::chars(&north_up).with_caption(format!("{caption} · weight {}", get_weight(grid)))
 }
 
 fn cycle(mut grid: Grid) -> Grid {
@@ -71,7 +71,9 @@ fn cycle(mut grid: Grid) -> Grid {
 
 pub fn part_one(input: &str) -> Option<u64> {
     let mut grid = get_grid(input);
+    visualize::frame(|| grid_frame(&grid, "input"));
     grid = slide(&grid);
+    visualize::frame(|| grid_frame(&grid, "tilted north"));
     let result = get_weight(&grid);
     Some(result)
 }
@@ -81,6 +83,7 @@ pub fn part_two(input: &str) -> Option<u64> {
     let mut cache = vec![grid.clone()];
     loop {
         grid = cycle(grid);
+        visualize::frame(|| grid_frame(&grid, &format!("cycle {}", cache.len())));
         if let Some(i) = cache.iter().position(|g| g == &grid) {
             let cycle_length = cache.len() - i;
             let index = i + (1_000_000_000 - i) % cycle_length;
@@ -96,13 +99,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(136));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(64));
     }
 }
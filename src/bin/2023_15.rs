@@ -1,9 +1,10 @@
+use advent_of_code::template::error::InputError;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-use crate::Operation::{Place, Remove};
+use self::Operation::{Place, Remove};
 
-advent_of_code::solution!(15);
+advent_of_code::solution!(2023, 15);
 
 #[derive(Debug)]
 struct Lens<'a> {
@@ -18,18 +19,21 @@ enum Operation<'a> {
 }
 
 impl<'a> Operation<'a> {
-    fn from_str(input: &str) -> Operation {
+    fn from_str(input: &'a str) -> Result<Operation<'a>, String> {
         static OPT_REG: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(\w+)([=-])([1-9])?$").unwrap());
-        let wtf = OPT_REG.captures(input);
-        let captures = wtf.unwrap();
+        let captures = OPT_REG
+            .captures(input)
+            .ok_or_else(|| format!("expected `<label>=<focal length>` or `<label>-`, got `{input}`"))?;
         let label = &input[captures.get(1).unwrap().range()];
         let lens_power_group = captures.get(3);
-        return if lens_power_group.is_some() {
-            let lens_power = input[captures.get(3).unwrap().range()].parse::<i32>().unwrap();
-            Place(Lens { label: label, focal: lens_power })
-        } else {
-            Remove(label)
-        };
+        match (&captures[2], lens_power_group) {
+            ("=", Some(lens_power)) => {
+                let focal = lens_power.as_str().parse::<i32>().map_err(|e| e.to_string())?;
+                Ok(Place(Lens { label, focal }))
+            }
+            ("-", None) => Ok(Remove(label)),
+            _ => Err(format!("expected `<label>=<focal length>` or `<label>-`, got `{input}`")),
+        }
     }
 }
 
@@ -50,10 +54,27 @@ pub fn part_one(input: &str) -> Option<u64> {
         .into()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+fn parse_operations(input: &str) -> Result<Vec<Operation<'_>>, InputError> {
+    let line = input.trim_end();
+    let mut column = 1;
+    let mut operations = vec![];
+    for step in line.split(',') {
+        let operation = Operation::from_str(step).map_err(|e| {
+            InputError::new(1, "invalid operation")
+                .with_column(column)
+                .with_text(line)
+                .with_source(e)
+        })?;
+        operations.push(operation);
+        column += step.len() + 1;
+    }
+    Ok(operations)
+}
+
+pub fn part_two(input: &str) -> Result<Option<u64>, InputError> {
     const LENS_BOX: Vec<Lens> = Vec::new();
     let mut lens_boxes = [LENS_BOX; 256];   //[Vec<Lens>; 256]
-    for operation in input.split(',').map(|o| Operation::from_str(o)) {
+    for operation in parse_operations(input)? {
         match operation {
             Remove(label) => {
                 let _ = &lens_boxes[apply_hash(label) as usize].retain(|l| l.label != label);
@@ -77,7 +98,7 @@ pub fn part_two(input: &str) -> Option<u64> {
                 .sum::<usize>()
         )
         .sum::<usize>();
-    Some(result as u64)
+    Ok(Some(result as u64))
 }
 
 #[cfg(test)]
@@ -86,7 +107,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(1320));
     }
 
@@ -98,7 +119,19 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(145));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result.unwrap(), Some(145));
+    }
+
+    #[test]
+    fn test_invalid_operation() {
+        let error = parse_operations("rn=1,cm+2").unwrap_err();
+        assert_eq!(error.column, Some(6));
+    }
+
+    #[test]
+    fn test_mismatched_operator() {
+        assert!(Operation::from_str("ab=").is_err());
+        assert!(Operation::from_str("ab-5").is_err());
     }
 }
@@ -5,9 +5,9 @@ use itertools::Itertools;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-use crate::Rule::{Default, Inferior, Superior};
+use self::Rule::{Default, Inferior, Superior};
 
-advent_of_code::solution!(19);
+advent_of_code::solution!(2023, 19);
 
 type Part = HashMap<String, u32>;
 
@@ -169,13 +169,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(19114));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
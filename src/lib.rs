@@ -1,4 +1,6 @@
 mod day;
+mod puzzle;
 pub mod template;
 
 pub use day::*;
+pub use puzzle::*;
@@ -1,30 +1,106 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, download, examples, leaderboard, read, readme, scaffold, solve, start, status, verify,
+};
+use advent_of_code::template::isolated;
 use args::{parse, AppArguments};
 
+/// All scaffolded solutions, collected by `build.rs`.
+mod solutions {
+    use std::process;
+
+    use advent_of_code::template::registry::Solution;
+
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+    /// Returns the registry, or exits if the binary was built without the `solutions` feature.
+    pub fn get(command: &str) -> &'static [Solution] {
+        if !cfg!(feature = "solutions") {
+            eprintln!(
+                "`{command}` needs the solutions compiled in, run it with `cargo {command}` or build with `--features solutions`."
+            );
+            process::exit(1);
+        }
+        SOLUTIONS
+    }
+}
+
 mod args {
     use std::process;
 
-    use advent_of_code::Day;
+    use advent_of_code::{
+        default_year, parse_year,
+        template::{
+            bench_history::{self, CompareOptions},
+            input::InputSource,
+            limits::{parse_memory, parse_timeout, Limits},
+            record::OutputFormat,
+            reports::Report,
+            runner::{parse_bench_time, BenchOptions},
+            visualize::Visualize,
+        },
+        Day, PuzzleId,
+    };
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Readme {
+            year: u16,
+            leaderboard: Option<String>,
+        },
+        Leaderboard {
+            year: u16,
+            leaderboard: Option<String>,
+            day: Option<Day>,
+        },
+        Examples {
+            puzzle: PuzzleId,
+            force: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
+            examples: usize,
+            template: Option<String>,
+        },
+        Start {
+            puzzle: PuzzleId,
+            template: Option<String>,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
-            time: bool,
+            time: Option<BenchOptions>,
             submit: Option<u8>,
+            format: OutputFormat,
+            input: Option<InputSource>,
+            watch: bool,
+            visualize: Option<Visualize>,
+            alloc_stats: bool,
         },
         All {
+            year: u16,
             release: bool,
-            time: bool,
+            time: Option<BenchOptions>,
+            compare: Option<CompareOptions>,
+            jobs: Option<usize>,
+            limits: Limits,
+            reports: Vec<Report>,
+        },
+        /// Runs a day for `all` in a child process, see [`isolated`](advent_of_code::template::isolated).
+        RunDay {
+            puzzle: PuzzleId,
+            time: Option<BenchOptions>,
+        },
+        Verify {
+            year: u16,
+        },
+        Status {
+            year: u16,
+            run_tests: bool,
         },
     }
 
@@ -33,23 +109,66 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: get_year(&mut args)?,
                 release: args.contains("--release"),
-                time: args.contains("--time"),
+                time: parse_bench_options(&mut args)?,
+                compare: parse_compare(&mut args)?,
+                jobs: args.opt_value_from_str(["-j", "--jobs"])?,
+                limits: Limits {
+                    timeout: args.opt_value_from_fn("--timeout", parse_timeout)?,
+                    memory: args.opt_value_from_fn("--memory-limit", parse_memory)?,
+                },
+                reports: args.values_from_str("--report")?,
+            },
+            Some("run-day") => AppArguments::RunDay {
+                puzzle: get_puzzle(&mut args)?,
+                time: parse_bench_options(&mut args)?,
             },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: get_puzzle(&mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: get_puzzle(&mut args)?,
+            },
+            Some("readme") => AppArguments::Readme {
+                year: get_year(&mut args)?,
+                leaderboard: args.opt_value_from_str("--leaderboard")?,
+            },
+            Some("leaderboard") => AppArguments::Leaderboard {
+                year: get_year(&mut args)?,
+                leaderboard: args.opt_value_from_str("--leaderboard")?,
+                day: args.opt_value_from_str("--day")?,
+            },
+            Some("examples") => AppArguments::Examples {
+                puzzle: get_puzzle(&mut args)?,
+                force: args.contains("--force"),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: get_puzzle(&mut args)?,
+                examples: args.opt_value_from_str("--examples")?.unwrap_or(1),
+                template: args.opt_value_from_str("--template")?,
+            },
+            Some("start") => AppArguments::Start {
+                puzzle: get_puzzle(&mut args)?,
+                template: args.opt_value_from_str("--template")?,
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                puzzle: get_puzzle(&mut args)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+                time: parse_bench_options(&mut args)?,
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                input: args.opt_value_from_str("--input")?,
+                watch: args.contains("--watch"),
+                visualize: parse_visualize(&mut args)?,
+                alloc_stats: args.contains("--alloc-stats"),
+            },
+            Some("verify") => AppArguments::Verify {
+                year: get_year(&mut args)?,
+            },
+            Some("status") => AppArguments::Status {
+                year: get_year(&mut args)?,
+                run_tests: !args.contains("--no-tests"),
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -68,6 +187,68 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses `--year`, falling back to the `AOC_YEAR` environment variable.
+    fn get_year(args: &mut pico_args::Arguments) -> Result<u16, Box<dyn std::error::Error>> {
+        match args.opt_value_from_fn("--year", parse_year)? {
+            Some(year) => Ok(year),
+            None => {
+                default_year().ok_or_else(|| "no year given, pass `--year` or set AOC_YEAR.".into())
+            }
+        }
+    }
+
+    /// Parses the day of a puzzle along with its year. The year has to be parsed first, so it is not mistaken for the day.
+    fn get_puzzle(args: &mut pico_args::Arguments) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        let year = get_year(args)?;
+        let day = args.free_from_str()?;
+        Ok(PuzzleId { year, day })
+    }
+
+    /// Parses `--time` along with the options that control benchmarking.
+    fn parse_bench_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<BenchOptions>, pico_args::Error> {
+        let time = args.contains("--time");
+        let default = BenchOptions::default();
+
+        let options = BenchOptions {
+            bench_time: args
+                .opt_value_from_fn("--bench-time", parse_bench_time)?
+                .unwrap_or(default.bench_time),
+            min_samples: args
+                .opt_value_from_str("--min-samples")?
+                .unwrap_or(default.min_samples),
+        };
+
+        Ok(time.then_some(options))
+    }
+
+    /// Parses `--compare`, its regression threshold in percent and `--accept`.
+    fn parse_compare(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<CompareOptions>, pico_args::Error> {
+        let compare = args.contains("--compare");
+        let threshold = args
+            .opt_value_from_str("--threshold")?
+            .unwrap_or(bench_history::DEFAULT_THRESHOLD);
+        let accept = args.contains("--accept");
+
+        Ok(compare.then_some(CompareOptions { threshold, accept }))
+    }
+
+    /// Parses `--visualize`, or `--visualize-dir` to write the frames to a directory instead.
+    fn parse_visualize(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Visualize>, pico_args::Error> {
+        let terminal = args.contains("--visualize");
+        let dir: Option<String> = args.opt_value_from_str("--visualize-dir")?;
+
+        Ok(match dir {
+            Some(dir) => Some(Visualize::Directory(dir.into())),
+            None => terminal.then_some(Visualize::Terminal),
+        })
+    }
 }
 
 fn main() {
@@ -77,16 +258,69 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
+            AppArguments::All {
+                year,
+                release,
+                time,
+                compare,
+                jobs,
+                limits,
+                reports,
+            } => all::handle(
+                solutions::get("all"),
+                year,
+                release,
+                time,
+                compare,
+                jobs,
+                limits,
+                reports,
+            ),
+            AppArguments::RunDay { puzzle, time } => {
+                isolated::serve(solutions::get("all"), puzzle, time);
+            }
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Readme { year, leaderboard } => {
+                readme::handle(year, leaderboard.as_deref());
+            }
+            AppArguments::Leaderboard {
+                year,
+                leaderboard,
                 day,
+            } => leaderboard::handle(year, leaderboard.as_deref(), day),
+            AppArguments::Examples { puzzle, force } => examples::handle(puzzle, force),
+            AppArguments::Scaffold {
+                puzzle,
+                examples,
+                template,
+            } => scaffold::handle(puzzle, examples, template.as_deref()),
+            AppArguments::Start { puzzle, template } => {
+                start::handle(puzzle, template.as_deref());
+            }
+            AppArguments::Solve {
+                puzzle,
+                release,
+                time,
+                submit,
+                format,
+                input,
+                watch,
+                visualize,
+                alloc_stats,
+            } => solve::handle(
+                puzzle,
                 release,
                 time,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                format,
+                input,
+                watch,
+                visualize,
+                alloc_stats,
+            ),
+            AppArguments::Verify { year } => verify::handle(solutions::get("verify"), year),
+            AppArguments::Status { year, run_tests } => status::handle(year, run_tests),
         },
     };
 }
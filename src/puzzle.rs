@@ -0,0 +1,129 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::Day;
+
+/// The first year of advent of code.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// Identifies a puzzle by its year and day.
+///
+/// # Display
+/// This value displays as the year followed by the two digit day.
+///
+/// ```
+/// # use advent_of_code::{Day, PuzzleId};
+/// let puzzle = PuzzleId::new(2023, Day::new(8).unwrap()).unwrap();
+/// assert_eq!(puzzle.to_string(), "2023/08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: u16,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    /// Creates a [`PuzzleId`] if the year is not before the first advent of code,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16, day: Day) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self { year, day })
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// The name of the binary that holds the solution, e.g. `2023_08`.
+    pub fn bin_name(self) -> String {
+        format!("{}_{}", self.year, self.day)
+    }
+
+    /// Parses the name of a solution binary, see [`PuzzleId::bin_name`].
+    pub fn from_bin_name(name: &str) -> Option<Self> {
+        let (year, day) = name.split_once('_')?;
+        Self::new(year.parse().ok()?, day.parse().ok()?)
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns the year set by the `AOC_YEAR` environment variable, which `.cargo/config.toml` provides for cargo commands.
+pub fn default_year() -> Option<u16> {
+    env::var("AOC_YEAR")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .filter(|year| *year >= FIRST_YEAR)
+}
+
+/// Parses a year passed on the command-line.
+pub fn parse_year(s: &str) -> Result<u16, YearFromStrError> {
+    s.parse()
+        .ok()
+        .filter(|year| *year >= FIRST_YEAR)
+        .ok_or(YearFromStrError)
+}
+
+/// An error which can be returned when parsing a year.
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year starting from {FIRST_YEAR}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`PuzzleId`] value in a const context.
+#[macro_export]
+macro_rules! puzzle {
+    ($year:expr, $day:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= $crate::FIRST_YEAR,
+            concat!("invalid year `", $year, "`, expecting 2015 or later"),
+        );
+        $crate::PuzzleId::__new_unchecked($year, $crate::day!($day))
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_year, PuzzleId};
+    use crate::puzzle;
+
+    #[test]
+    fn parses_bin_names() {
+        let puzzle = puzzle!(2022, 7);
+        assert_eq!(puzzle.bin_name(), "2022_07");
+        assert_eq!(PuzzleId::from_bin_name("2022_07"), Some(puzzle));
+        assert_eq!(PuzzleId::from_bin_name("2022_26"), None);
+        assert_eq!(PuzzleId::from_bin_name("1999_01"), None);
+        assert_eq!(PuzzleId::from_bin_name("07"), None);
+    }
+
+    #[test]
+    fn parses_years() {
+        assert_eq!(parse_year("2022").ok(), Some(2022));
+        assert!(parse_year("2014").is_err());
+        assert!(parse_year("twenty").is_err());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,159 @@
+/// Opt-in global allocator that counts heap usage, enabled with the `alloc_stats` feature.
+/// The counters are shared by all threads, so measurements are only meaningful while a single part runs at a time.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[cfg(feature = "alloc_stats")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Bytes that are currently allocated.
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+/// Highest value of [`CURRENT`] since the last measurement started.
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+/// Number of allocations, including reallocations.
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the system allocator and keeps track of allocated bytes and the number of allocations.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn add(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn remove(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::add(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::add(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::remove(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::remove(layout.size());
+            Self::add(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Heap usage of a single run of a part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Most bytes allocated at the same time, on top of what was allocated before the run.
+    pub peak_bytes: usize,
+    pub allocations: usize,
+}
+
+/// Whether heap usage is counted, i.e. the `alloc_stats` feature is enabled.
+#[must_use]
+pub fn is_enabled() -> bool {
+    cfg!(feature = "alloc_stats")
+}
+
+/// Runs `f` and measures its heap usage. Returns [`None`] for the stats if counting is not enabled.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    if !is_enabled() {
+        return (f(), None);
+    }
+
+    let base = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+
+    let output = f();
+
+    let stats = MemoryStats {
+        peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(base),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+    };
+
+    (output, Some(stats))
+}
+
+/// Formats a number of bytes for display, e.g. `1.5KB` or `12.0MB`.
+#[must_use]
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes}B")
+    } else {
+        format!("{value:.1}{}", UNITS[unit])
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_bytes;
+
+    /// Run with `cargo test --features test_lib,alloc_stats`.
+    #[cfg(feature = "alloc_stats")]
+    #[test]
+    fn measures_allocating_parts() {
+        let (len, first) = super::measure(|| {
+            let values: Vec<u64> = (0..1 << 17).collect();
+            let strings: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+            values.len() + strings.len()
+        });
+        let first = first.unwrap();
+
+        assert_eq!(len, (1 << 17) + 100);
+        assert!(first.peak_bytes >= (1 << 17) * 8, "{first:?}");
+        assert!(first.allocations >= 100, "{first:?}");
+
+        // the peak of the previous measurement must not carry over. the counters are shared with
+        // the tests running in parallel, so only compare against the first peak.
+        let (_, second) = super::measure(|| vec![0_u8; 1024]);
+        let second = second.unwrap();
+        assert!(second.peak_bytes >= 1024, "{second:?}");
+        assert!(
+            second.peak_bytes < first.peak_bytes / 4,
+            "{first:?} {second:?}"
+        );
+        assert!(second.allocations >= 1, "{second:?}");
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1536), "1.5KB");
+        assert_eq!(format_bytes(12 << 20), "12.0MB");
+        assert_eq!(format_bytes(3 << 30), "3.0GB");
+    }
+}
@@ -0,0 +1,136 @@
+/// Module that stores confirmed answers, so solutions can be checked against them after refactors.
+/// Answers of a day live in `data/{year}/answers/{day}.txt`, with one `part_1: <answer>` or `part_2: <answer>` line per part.
+use std::{fs, io, path::Path};
+
+use crate::template::get_data_path;
+use crate::template::record::Record;
+use crate::PuzzleId;
+
+/// The confirmed answers of a day. Parts without a confirmed answer are [`None`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_1 = Some(answer.into()),
+            2 => self.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.part_1.is_none() && self.part_2.is_none()
+    }
+
+    /// Checks an answer against the confirmed answer of a part.
+    /// Returns [`None`] if there is no confirmed answer for the part.
+    #[must_use]
+    pub fn check(&self, part: u8, answer: Option<&str>) -> Option<bool> {
+        self.get(part).map(|expected| answer == Some(expected))
+    }
+
+    fn parse(s: &str) -> Self {
+        let mut answers = Self::default();
+
+        for line in s.lines() {
+            if let Some((key, value)) = line.split_once(':') {
+                match key.trim() {
+                    "part_1" => answers.set(1, value.trim()),
+                    "part_2" => answers.set(2, value.trim()),
+                    _ => {}
+                }
+            }
+        }
+
+        answers
+    }
+
+    fn serialize(&self) -> String {
+        [(1, &self.part_1), (2, &self.part_2)]
+            .iter()
+            .filter_map(|(part, answer)| Some(format!("part_{part}: {}\n", answer.as_ref()?)))
+            .collect()
+    }
+}
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId) -> String {
+    get_data_path("answers", puzzle, "txt")
+}
+
+/// Reads the confirmed answers of a day. A missing file means that no answers are known yet.
+pub fn read(puzzle: PuzzleId) -> Result<Answers, io::Error> {
+    read_path(&get_path(puzzle))
+}
+
+/// Writes the confirmed answers of a day, creating the answers directory if necessary.
+pub fn write(puzzle: PuzzleId, answers: &Answers) -> Result<(), io::Error> {
+    write_path(&get_path(puzzle), answers)
+}
+
+/// Reads answers from any file in the answers format, e.g. the expected answers of an example.
+pub(crate) fn read_path(path: &str) -> Result<Answers, io::Error> {
+    if !Path::new(path).exists() {
+        return Ok(Answers::default());
+    }
+
+    Ok(Answers::parse(&fs::read_to_string(path)?))
+}
+
+pub(crate) fn write_path(path: &str, answers: &Answers) -> Result<(), io::Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, answers.serialize())
+}
+
+/// Marks a record as verified or not, depending on the confirmed answers of its day.
+pub fn verify(record: &mut Record) {
+    record.verified = read(record.puzzle)
+        .ok()
+        .and_then(|answers| answers.check(record.part, record.answer.as_deref()));
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+
+    #[test]
+    fn parses_answers() {
+        let answers = Answers::parse("part_1: 42\npart_2:  abc def \n");
+        assert_eq!(answers.get(1), Some("42"));
+        assert_eq!(answers.get(2), Some("abc def"));
+        assert_eq!(Answers::parse(&answers.serialize()), answers);
+    }
+
+    #[test]
+    fn parses_partial_answers() {
+        let answers = Answers::parse("# comment\n\npart_2: 7\n");
+        assert_eq!(answers.get(1), None);
+        assert_eq!(answers.get(2), Some("7"));
+        assert_eq!(answers.serialize(), "part_2: 7\n");
+    }
+
+    #[test]
+    fn checks_answers() {
+        let answers = Answers::parse("part_1: 42\n");
+        assert_eq!(answers.check(1, Some("42")), Some(true));
+        assert_eq!(answers.check(1, Some("43")), Some(false));
+        assert_eq!(answers.check(1, None), Some(false));
+        assert_eq!(answers.check(2, Some("42")), None);
+    }
+}
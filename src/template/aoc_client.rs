@@ -0,0 +1,279 @@
+/// Native client for the Advent of Code website.
+/// The session token is read from the `AOC_SESSION` environment variable, or from the file at `AOC_SESSION_FILE`
+/// (defaults to `~/.adventofcode.session`). The server can be changed with `AOC_BASE_URL`, e.g. to test against a local stand-in.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::submissions::Verdict;
+use crate::template::{get_data_path, markdown};
+use crate::PuzzleId;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (github.com/fspoettel/advent-of-code-rust)"
+);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    /// The server responded with an error status. Carries the status code and the body of the response.
+    Http(u16, String),
+    /// The request did not reach the server or the response could not be read.
+    Transport(String),
+    UnexpectedResponse(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session token found. Set AOC_SESSION or create \"{}\".",
+                get_session_path().map_or("~/.adventofcode.session".into(), |p| p
+                    .display()
+                    .to_string())
+            ),
+            AocClientError::Http(status, body) => {
+                write!(f, "server responded with status {status}")?;
+                match body
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty() && !line.starts_with('<'))
+                {
+                    Some(line) => write!(f, ": {line}"),
+                    None => write!(f, "."),
+                }
+            }
+            AocClientError::Transport(message) => write!(f, "request failed: {message}"),
+            AocClientError::UnexpectedResponse(message) => {
+                write!(f, "unexpected response: {message}")
+            }
+            AocClientError::IO(e) => write!(f, "could not write output files to file system: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => {
+                AocClientError::Http(status, response.into_string().unwrap_or_default())
+            }
+            ureq::Error::Transport(transport) => AocClientError::Transport(transport.to_string()),
+        }
+    }
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.into(),
+        }
+    }
+
+    /// Creates a client from the environment, see the module documentation.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = get_session().ok_or(AocClientError::SessionNotFound)?;
+        Ok(Self::new(&base_url, &session))
+    }
+
+    pub fn get_input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", puzzle_path(puzzle)))
+    }
+
+    /// Fetches the description of a puzzle and converts it to markdown.
+    /// The description of part two is included once part one is solved.
+    pub fn get_puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.get(&puzzle_path(puzzle))?;
+        let articles = extract_articles(&html);
+
+        if articles.is_empty() {
+            return Err(AocClientError::UnexpectedResponse(
+                "no puzzle description found.".into(),
+            ));
+        }
+
+        Ok(articles
+            .iter()
+            .map(|article| markdown::from_html(article))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    /// Fetches the JSON of the private leaderboard owned by the user `id`.
+    pub fn get_leaderboard(&self, year: u16, id: u64) -> Result<String, AocClientError> {
+        self.get(&format!("/{year}/leaderboard/private/view/{id}.json"))
+    }
+
+    /// Submits an answer. Returns the verdict along with the message of the server, converted to markdown.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<(Verdict, String), AocClientError> {
+        let html = self
+            .agent
+            .post(&self.url(&format!("{}/answer", puzzle_path(puzzle))))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        let message = extract_articles(&html)
+            .first()
+            .map(|article| markdown::from_html(article))
+            .ok_or_else(|| AocClientError::UnexpectedResponse("no message found.".into()))?;
+
+        Ok((Verdict::from_response(&message), message))
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocClientError> {
+        Ok(self
+            .agent
+            .get(&self.url(path))
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?
+            .into_string()?)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+}
+
+/// Downloads the input and the puzzle description of a day to the data folder.
+pub fn download(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let input_path = get_data_path("inputs", puzzle, "txt");
+    let puzzle_path = get_data_path("puzzles", puzzle, "md");
+
+    write_file(&input_path, &client.get_input(puzzle)?)?;
+    write_file(&puzzle_path, &client.get_puzzle(puzzle)?)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Downloads the puzzle description of a day and prints it to the terminal.
+pub fn read(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let description = client.get_puzzle(puzzle)?;
+    write_file(&get_data_path("puzzles", puzzle, "md"), &description)?;
+    println!("{description}");
+    Ok(())
+}
+
+/// Submits an answer and prints the message of the server.
+pub fn submit(puzzle: PuzzleId, part: u8, answer: &str) -> Result<Verdict, AocClientError> {
+    let client = AocClient::from_env()?;
+    let (verdict, message) = client.submit(puzzle, part, answer)?;
+    println!("{message}");
+    Ok(verdict)
+}
+
+/// The path of a puzzle on the website, e.g. `/2023/day/1`.
+fn puzzle_path(puzzle: PuzzleId) -> String {
+    format!("/{}/day/{}", puzzle.year, puzzle.day.into_inner())
+}
+
+pub(crate) fn write_file(path: &str, contents: &str) -> Result<(), io::Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+fn get_session_path() -> Option<PathBuf> {
+    if let Ok(path) = env::var("AOC_SESSION_FILE") {
+        return Some(path.into());
+    }
+
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".adventofcode.session"))
+}
+
+fn get_session() -> Option<String> {
+    let session = match env::var("AOC_SESSION") {
+        Ok(session) => session,
+        Err(_) => fs::read_to_string(get_session_path()?).ok()?,
+    };
+
+    parse_session(&session)
+}
+
+/// Accepts the bare token as well as the `session=<token>` form of the cookie.
+fn parse_session(s: &str) -> Option<String> {
+    let session = s.trim();
+    let session = session.strip_prefix("session=").unwrap_or(session);
+    (!session.is_empty()).then(|| session.into())
+}
+
+/// Returns the inner HTML of all `<article>` elements of a page.
+fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(len) = rest[content_start..].find("</article>") else {
+            break;
+        };
+        articles.push(&rest[content_start..content_start + len]);
+        rest = &rest[content_start + len..];
+    }
+
+    articles
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract_articles, parse_session};
+
+    #[test]
+    fn parses_sessions() {
+        assert_eq!(parse_session("abc123\n"), Some("abc123".into()));
+        assert_eq!(parse_session("session=abc123"), Some("abc123".into()));
+        assert_eq!(parse_session("  \n"), None);
+    }
+
+    #[test]
+    fn extracts_articles() {
+        let html = "<main><article class=\"day-desc\"><h2>A</h2></article><p>x</p><article><p>B</p></article></main>";
+        assert_eq!(extract_articles(html), vec!["<h2>A</h2>", "<p>B</p>"]);
+        assert!(extract_articles("<main></main>").is_empty());
+    }
+}
@@ -0,0 +1,292 @@
+/// Module that keeps a history of benchmark results and detects regressions against it.
+/// Every timed release run appends one entry per solved part to a JSON lines file. Parts that regressed are left out,
+/// unless the regression is accepted, so a slow run does not become the baseline of the next comparison.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::record::{Record, Status};
+use crate::{Day, PuzzleId};
+
+pub static HISTORY_PATH: &str = "data/bench_history.jsonl";
+
+/// Default threshold for regressions, in percent of the baseline median.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Options of `--compare`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompareOptions {
+    /// Relative change of the median that counts as a regression, in percent.
+    pub threshold: f64,
+    /// Whether timings that regressed are recorded anyway, becoming the new baseline.
+    pub accept: bool,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// The median timing of one solution part at a given revision.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub revision: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub puzzle: PuzzleId,
+    pub part: u8,
+    pub median: Duration,
+    pub samples: u128,
+}
+
+impl Entry {
+    #[allow(clippy::cast_precision_loss)]
+    fn to_json(&self) -> String {
+        let mut object: HashMap<String, JsonValue> = HashMap::new();
+        object.insert("revision".into(), self.revision.clone().into());
+        object.insert("timestamp".into(), (self.timestamp as f64).into());
+        object.insert("year".into(), f64::from(self.puzzle.year).into());
+        object.insert("day".into(), f64::from(self.puzzle.day.into_inner()).into());
+        object.insert("part".into(), f64::from(self.part).into());
+        object.insert(
+            "median_nanos".into(),
+            (self.median.as_nanos() as f64).into(),
+        );
+        object.insert("samples".into(), (self.samples as f64).into());
+        JsonValue::from(object).stringify().unwrap()
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn from_json(line: &str) -> Option<Self> {
+        let value: JsonValue = line.parse().ok()?;
+        let object: &HashMap<String, JsonValue> = value.get()?;
+        let number = |key: &str| -> Option<f64> { object.get(key)?.get::<f64>().copied() };
+
+        Some(Entry {
+            revision: object.get("revision")?.get::<String>()?.clone(),
+            timestamp: number("timestamp")? as u64,
+            puzzle: PuzzleId::new(number("year")? as u16, Day::new(number("day")? as u8)?)?,
+            part: number("part")? as u8,
+            median: Duration::from_nanos(number("median_nanos")? as u64),
+            samples: number("samples")? as u128,
+        })
+    }
+}
+
+/// The timing of a solution part compared against its baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub puzzle: PuzzleId,
+    pub part: u8,
+    pub baseline: Duration,
+    pub current: Duration,
+    /// Relative change of the median, in percent.
+    pub change: f64,
+    pub is_regression: bool,
+}
+
+/// Creates history entries for all solved parts in `records`.
+#[must_use]
+pub fn entries_from_records(records: &[Record], revision: &str, timestamp: u64) -> Vec<Entry> {
+    records
+        .iter()
+        .filter(|record| record.status == Status::Solved)
+        .map(|record| Entry {
+            revision: revision.into(),
+            timestamp,
+            puzzle: record.puzzle,
+            part: record.part,
+            median: record.duration,
+            samples: record.samples,
+        })
+        .collect()
+}
+
+/// Compares current entries against the most recent entry of the same part in `history`.
+/// Parts without a baseline are skipped.
+#[must_use]
+pub fn compare(history: &[Entry], current: &[Entry], threshold: f64) -> Vec<Comparison> {
+    current
+        .iter()
+        .filter_map(|entry| {
+            let baseline = history
+                .iter()
+                .filter(|x| x.puzzle == entry.puzzle && x.part == entry.part)
+                .max_by_key(|x| x.timestamp)?;
+
+            let baseline_nanos = baseline.median.as_nanos().max(1) as f64;
+            let change = (entry.median.as_nanos() as f64 - baseline_nanos) / baseline_nanos * 100.0;
+
+            Some(Comparison {
+                puzzle: entry.puzzle,
+                part: entry.part,
+                baseline: baseline.median,
+                current: entry.median,
+                change,
+                is_regression: change > threshold,
+            })
+        })
+        .collect()
+}
+
+/// Returns the entries that may be appended to the history: all entries except the ones that regressed.
+#[must_use]
+pub fn without_regressions(current: &[Entry], comparisons: &[Comparison]) -> Vec<Entry> {
+    current
+        .iter()
+        .filter(|entry| {
+            !comparisons
+                .iter()
+                .any(|c| c.is_regression && c.puzzle == entry.puzzle && c.part == entry.part)
+        })
+        .cloned()
+        .collect()
+}
+
+fn parse_history(s: &str) -> Result<Vec<Entry>, Error> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            Entry::from_json(line).ok_or_else(|| {
+                Error::Parser(format!(
+                    "malformed entry on line {} of benchmark history.",
+                    i + 1
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Reads all entries from the history file. A missing file is treated as an empty history.
+pub fn load() -> Result<Vec<Entry>, Error> {
+    if !Path::new(HISTORY_PATH).exists() {
+        return Ok(vec![]);
+    }
+
+    parse_history(&fs::read_to_string(HISTORY_PATH)?)
+}
+
+/// Appends entries to the history file, creating it if necessary.
+pub fn append(entries: &[Entry]) -> Result<(), Error> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_PATH)?;
+
+    for entry in entries {
+        writeln!(file, "{}", entry.to_json())?;
+    }
+
+    Ok(())
+}
+
+/// Describes the checked out git revision, e.g. `1a2b3c4` or `1a2b3c4-dirty`.
+#[must_use]
+pub fn current_revision() -> String {
+    Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map_or_else(|| "unknown".into(), |s| s.trim().to_string())
+}
+
+#[must_use]
+pub fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, parse_history, without_regressions, Entry};
+    use crate::puzzle;
+
+    fn entry(revision: &str, timestamp: u64, part: u8, nanos: u64) -> Entry {
+        Entry {
+            revision: revision.into(),
+            timestamp,
+            puzzle: puzzle!(2023, 5),
+            part,
+            median: Duration::from_nanos(nanos),
+            samples: 100,
+        }
+    }
+
+    #[test]
+    fn parses_history() {
+        let history = [entry("abc", 1, 1, 100), entry("def-dirty", 2, 2, 2000)];
+        let s = history
+            .iter()
+            .map(Entry::to_json)
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(parse_history(&s).unwrap(), history);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_on_malformed_history() {
+        parse_history("{\"revision\": \"abc\"}").unwrap();
+    }
+
+    #[test]
+    fn compares_against_latest_entry() {
+        let history = [entry("old", 1, 1, 50), entry("new", 2, 1, 100)];
+        let result = compare(&history, &[entry("cur", 3, 1, 105)], 10.0);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].baseline, Duration::from_nanos(100));
+        assert_eq!(result[0].is_regression, false);
+    }
+
+    #[test]
+    fn flags_regressions_above_threshold() {
+        let history = [entry("old", 1, 1, 100), entry("old", 1, 2, 100)];
+        let current = [entry("cur", 2, 1, 111), entry("cur", 2, 2, 90)];
+        let result = compare(&history, &current, 10.0);
+        assert_eq!(result[0].is_regression, true);
+        assert_eq!(result[1].is_regression, false);
+    }
+
+    #[test]
+    fn keeps_flagging_unaccepted_regressions() {
+        let mut history = vec![entry("old", 1, 1, 100), entry("old", 1, 2, 100)];
+
+        for (run, timestamp) in [("slow", 2), ("still-slow", 3)] {
+            let current = [entry(run, timestamp, 1, 150), entry(run, timestamp, 2, 100)];
+            let result = compare(&history, &current, 10.0);
+            assert_eq!(result[0].is_regression, true);
+            assert_eq!(result[0].baseline, Duration::from_nanos(100));
+
+            history.extend(without_regressions(&current, &result));
+        }
+
+        assert_eq!(history.iter().filter(|x| x.part == 1).count(), 1);
+        assert_eq!(history.iter().filter(|x| x.part == 2).count(), 3);
+    }
+
+    #[test]
+    fn skips_parts_without_baseline() {
+        let result = compare(&[entry("old", 1, 1, 100)], &[entry("cur", 2, 2, 100)], 10.0);
+        assert_eq!(result.is_empty(), true);
+    }
+}
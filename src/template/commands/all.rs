@@ -1,254 +1,508 @@
-use std::io;
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    num::NonZeroUsize,
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
 use crate::template::{
-    readme_benchmarks::{self, Timings},
+    alloc, answers,
+    bench_history::{self, CompareOptions},
+    get_data_path, isolated,
+    limits::{self, Limits, LimitsConfig},
+    readme_benchmarks::Timings,
+    record::{part_label, Record, Status, PARSE},
+    registry::{self, Solution},
+    reports::{Report, ReportFormat},
+    runner::{format_record, BenchOptions},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{all_days, PuzzleId};
+
+/// Runs all solutions. When `compare` is set, timings are checked against the benchmark history
+/// and the process exits with a non-zero status if any part regressed by more than its threshold (in percent).
+/// Up to `jobs` days run at the same time, their output is still printed in day order.
+/// Only the records are ordered, anything the solutions print themselves is interleaved between parallel days.
+/// Only the days of `year` are run. Days with time or memory caps run in a child process and are stopped once they exceed them,
+/// the caps passed in `limits` replace the global caps of the year.
+/// Timed runs write their timings to `reports`. If none are passed, timed release runs update the benchmark table of the readme.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    solutions: &[Solution],
+    year: u16,
+    is_release: bool,
+    time: Option<BenchOptions>,
+    compare: Option<CompareOptions>,
+    jobs: Option<usize>,
+    limits: Limits,
+    reports: Vec<Report>,
+) {
+    if compare.is_some() && time.is_none() {
+        eprintln!("`--compare` requires timings, run with `--time` or use `cargo time --compare`.");
+        process::exit(1);
+    }
 
-pub fn handle(is_release: bool, is_timed: bool) {
-    let mut timings: Vec<Timings> = vec![];
+    // the history only holds release timings, comparing others against it would be meaningless.
+    if compare.is_some() && !is_release {
+        eprintln!(
+            "`--compare` requires release timings, run with `--release` or use `cargo time --compare`."
+        );
+        process::exit(1);
+    }
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
-        }
+    if !reports.is_empty() && time.is_none() {
+        eprintln!("`--report` requires timings, run with `--time` or use `cargo time --report`.");
+        process::exit(1);
+    }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+    let limits = get_limits(year, limits);
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let mut timings: Vec<Timings> = vec![];
+    let mut records: Vec<Record> = vec![];
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+    let mut collect = |puzzle: PuzzleId, day_records: Option<Vec<Record>>| {
+        if let Some(day_records) = day_records {
+            timings.push(collect_timings(&day_records, puzzle));
+            records.extend(day_records);
         }
-    });
+    };
 
-    if is_timed {
+    let jobs = get_jobs(jobs, time.is_some() || alloc::is_enabled());
+    let puzzles: Vec<PuzzleId> = all_days().map(|day| PuzzleId { year, day }).collect();
+
+    println!("{ANSI_BOLD}Advent of Code {year}{ANSI_RESET}\n");
+
+    if jobs > 1 {
+        run_parallel(
+            solutions,
+            &puzzles,
+            time.as_ref(),
+            &limits,
+            jobs,
+            |puzzle, output, day_records| {
+                print!("{output}");
+                collect(puzzle, day_records);
+            },
+        );
+    } else {
+        puzzles.iter().for_each(|&puzzle| {
+            let day_records = run_day(solutions, puzzle, time.as_ref(), &limits, true, &mut |s| {
+                print!("{s}")
+            });
+            collect(puzzle, day_records);
+        });
+    }
+
+    if time.is_some() {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
-                }
-            }
+        // reports that were asked for are always written, only the readme and the history are reserved for release timings.
+        if is_release || !reports.is_empty() {
+            write_reports(&reports, &timings, total_millis);
+        }
+
+        if is_release && !update_history(&records, compare) {
+            process::exit(1);
         }
     }
 }
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
+/// Untimed runs use every core by default. Measured runs, which are timed or count allocations, are serial so days don't
+/// compete for resources or share the allocation counters.
+fn get_jobs(jobs: Option<usize>, is_measured: bool) -> usize {
+    match jobs {
+        Some(jobs) => {
+            if is_measured && jobs > 1 {
+                eprintln!("Warning: running days in parallel skews their measurements.");
+            }
+            jobs.max(1)
+        }
+        None if is_measured => 1,
+        None => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+    }
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+/// Reads the caps of a year and replaces its global caps with the ones passed on the command line.
+fn get_limits(year: u16, overrides: Limits) -> LimitsConfig {
+    let mut config = limits::read(year).unwrap_or_else(|e| {
+        eprintln!("Could not read \"{}\": {e}", limits::get_path(year));
+        process::exit(1);
+    });
+
+    config.global = config.global.or(overrides);
+
+    let has_memory_limit = config.global.memory.is_some()
+        || config.days.values().any(|limits| limits.memory.is_some());
+
+    if has_memory_limit && !limits::supports_memory_limit() {
+        eprintln!("Warning: memory limits are not supported on this platform and will be ignored.");
     }
+
+    config
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Runs days on `jobs` worker threads. Output of each day is buffered and handed to `on_day` in day order.
+fn run_parallel(
+    solutions: &[Solution],
+    days: &[PuzzleId],
+    time: Option<&BenchOptions>,
+    limits: &LimitsConfig,
+    jobs: usize,
+    mut on_day: impl FnMut(PuzzleId, String, Option<Vec<Record>>),
+) {
+    let next_day = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let next_day = &next_day;
+
+            scope.spawn(move || {
+                while let Some(&day) = days.get(next_day.fetch_add(1, Ordering::Relaxed)) {
+                    let mut output = String::new();
+                    let day_records = run_day(solutions, day, time, limits, false, &mut |s| {
+                        output.push_str(s);
+                    });
+                    if tx.send((day, output, day_records)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(tx);
+
+        // days finish out of order, hold back their output until all previous days have been printed.
+        let mut pending = BTreeMap::new();
+        let mut days_to_print = days.iter().peekable();
+
+        for (day, output, day_records) in rx {
+            pending.insert(day, (output, day_records));
+
+            while let Some((output, day_records)) =
+                days_to_print.peek().and_then(|day| pending.remove(*day))
+            {
+                on_day(*days_to_print.next().unwrap(), output, day_records);
+            }
+        }
+    });
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+/// Runs the registered solution for a day against its input and passes all output to `emit`.
+/// If `progress` is set, answers are printed to stdout before benching starts. Returns [`None`] if the day could not be run.
+/// Days with caps run in a child process, which reports all parts once they finished.
+fn run_day(
+    solutions: &[Solution],
+    puzzle: PuzzleId,
+    time: Option<&BenchOptions>,
+    limits: &LimitsConfig,
+    progress: bool,
+    emit: &mut dyn FnMut(&str),
+) -> Option<Vec<Record>> {
+    if puzzle.day > 1 {
+        emit("\n");
+    }
+
+    emit(&format!(
+        "{ANSI_BOLD}Day {}{ANSI_RESET}\n------\n",
+        puzzle.day
+    ));
+
+    let Some(solution) = registry::find(solutions, puzzle) else {
+        emit("Not solved.\n");
+        return None;
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    let input = match fs::read_to_string(get_path_for_input(puzzle)) {
+        Ok(input) => input,
+        Err(e) => {
+            emit(&format!("Could not read input file: {e}\n"));
+            return None;
         }
+    };
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    let day_limits = limits.for_day(puzzle.day);
 
-        if is_release {
-            args.push("--release");
+    if !day_limits.is_empty() {
+        let mut records = isolated::run_day(puzzle, solution.parts(), time, &day_limits);
+        for record in &mut records {
+            answers::verify(record);
+            emit(&format_record(record));
         }
+        return Some(records);
+    }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+    let mut records = vec![];
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+    solution.run_catching(&input, time, progress, &mut |mut record| {
+        answers::verify(&mut record);
+        if progress {
+            emit("\r");
+        }
+        emit(&format_record(&record));
+        records.push(record);
+    });
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+    Some(records)
+}
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+fn collect_timings(records: &[Record], puzzle: PuzzleId) -> Timings {
+    let mut timings = Timings {
+        puzzle,
+        parse: None,
+        part_1: None,
+        part_2: None,
+        part_1_memory: None,
+        part_2_memory: None,
+        total_nanos: 0_f64,
+    };
 
-        let mut output = vec![];
+    records
+        .iter()
+        .filter(|record| record.status == Status::Solved)
+        .for_each(|record| {
+            match record.part {
+                PARSE => timings.parse = Some(record.duration),
+                1 => {
+                    timings.part_1 = Some(record.duration);
+                    timings.part_1_memory = record.memory;
+                }
+                2 => {
+                    timings.part_2 = Some(record.duration);
+                    timings.part_2_memory = record.memory;
+                }
+                _ => {}
+            }
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+            #[allow(clippy::cast_precision_loss)]
+            {
+                timings.total_nanos += record.duration.as_nanos() as f64;
+            }
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+    timings
+}
 
-        thread.join().unwrap();
-        cmd.wait()?;
+fn write_reports(reports: &[Report], timings: &[Timings], total_millis: f64) {
+    let default = [Report::default()];
+    let reports = if reports.is_empty() {
+        &default
+    } else {
+        reports
+    };
 
-        Ok(output)
+    for report in reports {
+        match (report.write(timings, total_millis), report.format) {
+            (Ok(()), ReportFormat::Readme) => {
+                println!("Successfully updated {} with benchmarks.", report.path);
+            }
+            (Ok(()), _) => println!(
+                "Successfully wrote {} report to \"{}\".",
+                report.format, report.path
+            ),
+            (Err(e), _) => eprintln!("Failed to write report \"{report}\": {e:?}"),
+        }
     }
+}
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+/// Appends the timings of this run to the benchmark history, optionally comparing them against the stored baseline first.
+/// Timings that regressed are only appended if the regression is accepted. Returns `false` if a regression was detected.
+fn update_history(records: &[Record], compare: Option<CompareOptions>) -> bool {
+    let history = match bench_history::load() {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Failed to read benchmark history: {e:?}");
+            return compare.is_none();
+        }
+    };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+    let entries = bench_history::entries_from_records(
+        records,
+        &bench_history::current_revision(),
+        bench_history::current_timestamp(),
+    );
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+    let mut passed = true;
+    let mut entries_to_append = entries.clone();
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+    if let Some(CompareOptions { threshold, accept }) = compare {
+        println!("\n{ANSI_BOLD}Comparison{ANSI_RESET} (threshold: {threshold}%)");
 
-                timings.total_nanos += nanos;
-            });
+        let comparisons = bench_history::compare(&history, &entries, threshold);
+
+        if comparisons.is_empty() {
+            println!("No baseline found in \"{}\".", bench_history::HISTORY_PATH);
+        }
+
+        for c in &comparisons {
+            let status = if c.is_regression {
+                "✖ regression"
+            } else {
+                "✔"
+            };
+            println!(
+                "Day {} {}: {:.1?} → {:.1?} ({:+.1}%) {status}",
+                c.puzzle.day,
+                part_label(c.part),
+                c.baseline,
+                c.current,
+                c.change
+            );
+        }
+
+        passed = comparisons.iter().all(|c| !c.is_regression);
 
-        timings
+        if !accept {
+            entries_to_append = bench_history::without_regressions(&entries, &comparisons);
+        }
+
+        let held_back = entries.len() - entries_to_append.len();
+        if held_back > 0 {
+            println!("Not appending {held_back} regressed timing(s) to benchmark history, pass `--accept` to make them the new baseline.");
+        }
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    match bench_history::append(&entries_to_append) {
+        Ok(()) => println!("Successfully appended timings to benchmark history."),
+        Err(e) => eprintln!("Failed to append timings to benchmark history: {e:?}"),
+    }
+
+    passed
+}
+
+#[derive(Debug)]
+pub enum Error {
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
     }
+}
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
+#[must_use]
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{}.rs", puzzle.bin_name())
+}
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+#[must_use]
+pub fn get_path_for_input(puzzle: PuzzleId) -> String {
+    format!("./{}", get_data_path("inputs", puzzle, "txt"))
+}
 
-        Some((str_timing, parsed_timing))
+/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
+#[cfg(feature = "test_lib")]
+macro_rules! assert_approx_eq {
+    ($a:expr, $b:expr) => {{
+        let (a, b) = (&$a, &$b);
+        assert!(
+            (*a - *b).abs() < 1.0e-6,
+            "{} is not approximately equal to {}",
+            *a,
+            *b
+        );
+    }};
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{collect_timings, run_day};
+
+    use crate::puzzle;
+    use crate::template::limits::LimitsConfig;
+    use crate::template::record::{Record, Status};
+    use crate::template::registry::Solution;
+    use crate::template::runner::BenchOptions;
+
+    fn record(part: u8, answer: Option<&str>, nanos: u64) -> Record {
+        Record {
+            puzzle: puzzle!(2023, 1),
+            part,
+            answer: answer.map(Into::into),
+            duration: Duration::from_nanos(nanos),
+            samples: 100,
+            status: if answer.is_some() {
+                Status::Solved
+            } else {
+                Status::Unsolved
+            },
+            stats: None,
+            verified: None,
+            error: None,
+            memory: None,
+        }
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn test_well_formed() {
+        let res = collect_timings(
+            &[record(1, Some("0"), 74), record(2, Some("10"), 74_130_000)],
+            puzzle!(2023, 1),
+        );
+        assert_approx_eq!(res.total_nanos, 74130074_f64);
+        assert_eq!(res.part_1.unwrap(), Duration::from_nanos(74));
+        assert_eq!(format!("{:.1?}", res.part_2.unwrap()), "74.1ms");
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
+    #[test]
+    fn test_missing_parts() {
+        let res = collect_timings(
+            &[record(1, None, 10), record(2, None, 10)],
+            puzzle!(2023, 1),
+        );
+        assert_approx_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1.is_none(), true);
+        assert_eq!(res.part_2.is_none(), true);
+    }
 
-        use crate::day;
+    fn panicking(_: &str, _: Option<&BenchOptions>, _: bool, emit: &mut dyn FnMut(Record)) {
+        emit(record(1, Some("1"), 10));
+        panic!("invalid input");
+    }
 
-        #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
-
-        #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    fn solving(_: &str, _: Option<&BenchOptions>, _: bool, emit: &mut dyn FnMut(Record)) {
+        emit(record(1, Some("1"), 10));
+        emit(record(2, Some("2"), 10));
+    }
+
+    const SOLUTIONS: [Solution; 2] = [
+        Solution::new(puzzle!(2023, 7), false, panicking),
+        Solution::new(puzzle!(2023, 8), false, solving),
+    ];
+
+    #[test]
+    fn survives_panicking_days() {
+        let limits = LimitsConfig::default();
+        let mut output = String::new();
+
+        let records: Vec<Vec<Record>> = [puzzle!(2023, 7), puzzle!(2023, 8)]
+            .into_iter()
+            .map(|puzzle| {
+                run_day(&SOLUTIONS, puzzle, None, &limits, false, &mut |s| {
+                    output.push_str(s);
+                })
+                .unwrap()
+            })
+            .collect();
+
+        let statuses = |records: &[Record]| records.iter().map(|x| x.status).collect::<Vec<_>>();
+        assert_eq!(statuses(&records[0]), [Status::Solved, Status::Error]);
+        assert_eq!(
+            records[0][1].error.as_deref(),
+            Some("panicked: invalid input")
+        );
+        assert_eq!(statuses(&records[1]), [Status::Solved, Status::Solved]);
+        assert!(output.contains("Day 08"), "{output}");
     }
 }
@@ -0,0 +1,39 @@
+use std::{fs, process};
+
+use crate::template::{examples, get_data_path};
+use crate::PuzzleId;
+
+/// Extracts the examples and their expected answers from the downloaded puzzle description.
+/// Examples that are not empty are kept, unless `force` is set.
+pub fn handle(puzzle: PuzzleId, force: bool) {
+    let puzzle_path = get_data_path("puzzles", puzzle, "md");
+
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Could not read \"{puzzle_path}\", download the puzzle first with `cargo download {}`.",
+            puzzle.day
+        );
+        process::exit(1);
+    };
+
+    let found = examples::extract(&markdown);
+
+    if found.is_empty() {
+        eprintln!("No examples found in \"{puzzle_path}\".");
+        process::exit(1);
+    }
+
+    for (i, example) in found.iter().enumerate() {
+        match examples::write(puzzle, i + 1, example, force) {
+            Ok(written) => {
+                for path in written {
+                    println!("🎄 Successfully wrote example to \"{path}\".");
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to write example {}: {e}", i + 1);
+                process::exit(1);
+            }
+        }
+    }
+}
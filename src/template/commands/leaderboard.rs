@@ -0,0 +1,236 @@
+use std::{process, time::Duration};
+
+use colored::Colorize;
+
+use crate::template::leaderboard::{self, Leaderboard, Member};
+use crate::{all_days, Day};
+
+/// Prints the standings of a private leaderboard, followed by how long each member took for the stars of `day`.
+/// Without a day, the latest day that anyone earned a star on is shown.
+pub fn handle(year: u16, path: Option<&str>, day: Option<Day>) {
+    let leaderboard = match leaderboard::load(year, path) {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            eprintln!("failed to load leaderboard: {e}");
+            process::exit(1);
+        }
+    };
+
+    print!("{}", render(&leaderboard, day, leaderboard::now()));
+}
+
+fn render(leaderboard: &Leaderboard, day: Option<Day>, now: u64) -> String {
+    let mut members: Vec<&Member> = leaderboard.members.iter().collect();
+    members.sort_by(|a, b| {
+        b.local_score
+            .cmp(&a.local_score)
+            .then(b.stars.cmp(&a.stars))
+            .then(a.id.cmp(&b.id))
+    });
+
+    let days = shown_days(leaderboard, now);
+    let mut out = format!(
+        "{}\n\n",
+        format!(
+            "Advent of Code {} · private leaderboard of {} members",
+            leaderboard.year,
+            members.len()
+        )
+        .bold()
+    );
+
+    out.push_str(&render_standings(&members, &days));
+
+    if let Some(day) = day.or_else(|| latest_day(leaderboard)) {
+        out.push('\n');
+        out.push_str(&render_day(leaderboard.year, &members, day));
+    }
+
+    out
+}
+
+/// All days that are unlocked or that someone has a star for.
+fn shown_days(leaderboard: &Leaderboard, now: u64) -> Vec<Day> {
+    let unlocked = all_days().filter(|day| leaderboard::unlock_time(leaderboard.year, *day) <= now);
+    let last = unlocked
+        .chain(latest_day(leaderboard))
+        .max()
+        .map_or(0, Day::into_inner);
+
+    all_days().take(usize::from(last)).collect()
+}
+
+fn latest_day(leaderboard: &Leaderboard) -> Option<Day> {
+    leaderboard
+        .members
+        .iter()
+        .filter_map(|member| member.days.keys().last().copied())
+        .max()
+}
+
+fn render_standings(members: &[&Member], days: &[Day]) -> String {
+    let tens: String = days
+        .iter()
+        .map(|day| match day.into_inner() / 10 {
+            0 => ' ',
+            x => char::from(b'0' + x),
+        })
+        .collect();
+    let ones: String = days
+        .iter()
+        .map(|day| char::from(b'0' + day.into_inner() % 10))
+        .collect();
+
+    let prefix = " ".repeat(22);
+    let mut out = format!(
+        "{prefix}{}\n{}{}\n",
+        tens.dimmed(),
+        format!("{:>4}  {:>6}  {:>6}  ", "", "Score", "Stars").bold(),
+        ones.dimmed()
+    );
+
+    for (rank, member) in members.iter().enumerate() {
+        let stars: String = days
+            .iter()
+            .map(|day| {
+                let completion = member.days.get(day).copied().unwrap_or_default();
+                match (completion.part_1, completion.part_2) {
+                    (Some(_), Some(_)) => "*".bright_yellow().to_string(),
+                    (Some(_), None) => "*".white().to_string(),
+                    _ => "*".bright_black().to_string(),
+                }
+            })
+            .collect();
+
+        out.push_str(&format!(
+            "{:>4}  {:>6}  {:>6}  {stars}  {}\n",
+            format!("{})", rank + 1),
+            member.local_score,
+            member.stars,
+            member.display_name().bold()
+        ));
+    }
+
+    out
+}
+
+fn render_day(year: u16, members: &[&Member], day: Day) -> String {
+    let width = members
+        .iter()
+        .map(|member| member.display_name().chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut out = format!(
+        "{}\n{}\n",
+        format!("Day {} · time after unlock", day.into_inner()).bold(),
+        format!("{:width$}  {:>8}  {:>8}", "", "Part 1", "Part 2").bold()
+    );
+
+    let mut finishers: Vec<&Member> = members
+        .iter()
+        .filter(|member| member.days.contains_key(&day))
+        .copied()
+        .collect();
+    finishers.sort_by_key(|member| {
+        let completion = member.days[&day];
+        (
+            completion.part_2.is_none(),
+            completion.part_2,
+            completion.part_1,
+        )
+    });
+
+    if finishers.is_empty() {
+        out.push_str(&format!("{}\n", "No stars yet.".dimmed()));
+    }
+
+    for member in finishers {
+        let completion = member.days[&day];
+        let cell = |timestamp: Option<u64>| {
+            let text = timestamp.map_or_else(
+                || "-".into(),
+                |x| format_delta(leaderboard::time_to_star(year, day, x)),
+            );
+            format!("{text:>8}")
+        };
+
+        out.push_str(&format!(
+            "{:width$}  {}  {}\n",
+            member.display_name(),
+            cell(completion.part_1).white(),
+            cell(completion.part_2).bright_yellow()
+        ));
+    }
+
+    out
+}
+
+/// Formats a duration like the personal stats of the website, e.g. `01:02:03` or `>24h`.
+fn format_delta(delta: Duration) -> String {
+    let secs = delta.as_secs();
+    if secs >= 24 * 60 * 60 {
+        ">24h".into()
+    } else {
+        format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_delta, render};
+    use crate::day;
+    use crate::template::leaderboard::Leaderboard;
+    use crate::template::strip_ansi;
+
+    const LEADERBOARD: &str = r#"{
+        "event": "2023",
+        "owner_id": 3031,
+        "members": {
+            "3031": {
+                "id": 3031, "name": "Ferris", "stars": 3, "local_score": 10,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1701406830 }, "2": { "get_star_ts": 1701410462 } },
+                    "3": { "1": { "get_star_ts": 1701580000 } }
+                }
+            },
+            "42": { "id": 42, "name": null, "stars": 1, "local_score": 2,
+                "completion_day_level": { "1": { "1": { "get_star_ts": 1701600000 } } }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn formats_deltas() {
+        assert_eq!(format_delta(Duration::from_secs(30)), "00:00:30");
+        assert_eq!(format_delta(Duration::from_secs(3662)), "01:01:02");
+        assert_eq!(format_delta(Duration::from_secs(24 * 60 * 60)), ">24h");
+    }
+
+    #[test]
+    fn renders_leaderboards() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        // the morning of the third day, before the fourth day unlocks.
+        let out = strip_ansi(&render(&leaderboard, Some(day!(1)), 1_701_600_000));
+
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "Advent of Code 2023 · private leaderboard of 2 members",
+                "",
+                "                         ",
+                "       Score   Stars  123",
+                "  1)      10       3  ***  Ferris",
+                "  2)       2       1  ***  (anonymous user #42)",
+                "",
+                "Day 1 · time after unlock",
+                "                        Part 1    Part 2",
+                "Ferris                00:00:30  01:01:02",
+                "(anonymous user #42)      >24h         -",
+            ]
+        );
+    }
+}
@@ -1,5 +1,11 @@
 pub mod all;
 pub mod download;
+pub mod examples;
+pub mod leaderboard;
 pub mod read;
+pub mod readme;
 pub mod scaffold;
 pub mod solve;
+pub mod start;
+pub mod status;
+pub mod verify;
@@ -0,0 +1,22 @@
+use std::process;
+
+use crate::template::leaderboard;
+use crate::template::readme_stars;
+
+/// Updates the ⭐️ progress table in the readme. The private leaderboard is read from the JSON file at `path`,
+/// or downloaded if no path is given. Progress is shown for the user `AOC_USER_ID`, or the owner of the leaderboard.
+pub fn handle(year: u16, path: Option<&str>) {
+    if let Err(e) = update(year, path) {
+        eprintln!("failed to update readme stars: {e}");
+        process::exit(1);
+    }
+}
+
+fn update(year: u16, path: Option<&str>) -> Result<(), String> {
+    let leaderboard = leaderboard::load(year, path)?;
+    let member = leaderboard.current_member()?;
+
+    readme_stars::update(year, member).map_err(|e| format!("{e:?}"))?;
+    println!("Successfully updated README.md with {} ⭐️.", member.stars);
+    Ok(())
+}
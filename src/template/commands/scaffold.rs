@@ -1,12 +1,14 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::{examples, get_data_path};
+use crate::{default_year, PuzzleId};
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER);
 
 pub fn part_one(input: &str) -> Option<u32> {
     None
@@ -22,53 +24,96 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, PART_ONE_EXPECTED);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, PART_TWO_EXPECTED);
+    }
+
+    #[test]
+    fn test_examples() {
+        advent_of_code::template::examples::check(PUZZLE, 1, part_one);
+        advent_of_code::template::examples::check(PUZZLE, 2, part_two);
     }
 }
 "#;
 
+/// Directory that holds custom module templates, e.g. `templates/grid.rs` for `--template grid`.
+/// A `templates/default.rs` file replaces the built-in template.
+const TEMPLATES_DIR: &str = "templates";
+
+/// Loads the module template, either the named template or the default one.
+fn load_template(name: Option<&str>) -> Result<String, String> {
+    let path = format!("{TEMPLATES_DIR}/{}.rs", name.unwrap_or("default"));
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound && name.is_none() => {
+            Ok(MODULE_TEMPLATE.into())
+        }
+        Err(e) => Err(format!("could not read template \"{path}\": {e}")),
+    }
+}
+
+/// The title of a downloaded puzzle, e.g. `Day 1: Trebuchet?!`, or `Day 1` if the puzzle was not downloaded yet.
+fn puzzle_title(puzzle: PuzzleId) -> String {
+    fs::read_to_string(get_data_path("puzzles", puzzle, "md"))
+        .ok()
+        .and_then(|markdown| {
+            let heading = markdown.lines().find(|line| examples::is_heading(line))?;
+            let title = heading
+                .trim_start_matches("## ")
+                .trim_start_matches('\\')
+                .trim_matches(|c: char| c == '-' || c.is_whitespace());
+            Some(title.to_string())
+        })
+        .unwrap_or_else(|| format!("Day {}", puzzle.day.into_inner()))
+}
+
+/// The expected value of a scaffolded test, taken from the answers extracted for the first example.
+/// Answers that are not numbers are left out, since the template returns a number.
+fn expected_value(puzzle: PuzzleId, part: u8) -> String {
+    examples::read_answers(puzzle, 1)
+        .ok()
+        .and_then(|answers| answers.get(part).map(ToString::to_string))
+        .filter(|answer| answer.parse::<i64>().is_ok())
+        .map_or_else(|| "None".into(), |answer| format!("Some({answer})"))
+}
+
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    create_parent_dir(path)?;
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
-
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
+fn create_parent_dir(path: &str) -> Result<(), std::io::Error> {
+    match Path::new(path).parent() {
+        Some(parent) => fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+}
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+/// Creates the module of a day from a template, along with its input file and `example_count` example files.
+pub fn handle(puzzle: PuzzleId, example_count: usize, template: Option<&str>) {
+    if let Err(e) = create_module(puzzle, template) {
+        eprintln!("{e}");
+        process::exit(1);
     }
 
+    let input_path = get_data_path("inputs", puzzle, "txt");
+
     match create_file(&input_path) {
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
@@ -79,16 +124,66 @@ pub fn handle(day: Day) {
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    if let Err(e) = create_examples(puzzle, example_count) {
+        eprintln!("{e}");
+        process::exit(1);
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    print_solve_hint(puzzle);
+}
+
+pub(crate) fn get_module_path(puzzle: PuzzleId) -> String {
+    format!("src/bin/{}.rs", puzzle.bin_name())
+}
+
+/// Creates the module of a day from a template. Fails if the module already exists.
+pub(crate) fn create_module(puzzle: PuzzleId, template: Option<&str>) -> Result<(), String> {
+    let module_path = get_module_path(puzzle);
+    let template = load_template(template).map_err(|e| format!("Failed to load template: {e}"))?;
+
+    let mut file =
+        safe_create_file(&module_path).map_err(|e| format!("Failed to create module file: {e}"))?;
+
+    file.write_all(
+        template
+            .replace("YEAR_NUMBER", &puzzle.year.to_string())
+            .replace("DAY_NUMBER", &puzzle.day.into_inner().to_string())
+            .replace("PART_ONE_EXPECTED", &expected_value(puzzle, 1))
+            .replace("PART_TWO_EXPECTED", &expected_value(puzzle, 2))
+            .replace("PUZZLE_TITLE", &puzzle_title(puzzle))
+            .as_bytes(),
+    )
+    .map_err(|e| format!("Failed to write module contents: {e}"))?;
+
+    println!("Created module file \"{}\"", &module_path);
+    Ok(())
+}
+
+/// Creates empty example files, so the tests of a module can read them. Existing example files are kept.
+pub(crate) fn create_examples(puzzle: PuzzleId, example_count: usize) -> Result<(), String> {
+    for index in 1..=example_count.max(1) {
+        let example_path = examples::get_path(puzzle, index, "txt");
+
+        if Path::new(&example_path).exists() {
+            println!("Using existing example file \"{}\"", &example_path);
+            continue;
+        }
+
+        create_file(&example_path).map_err(|e| format!("Failed to create example file: {e}"))?;
+        println!("Created empty example file \"{}\"", &example_path);
+    }
+
+    Ok(())
+}
+
+pub(crate) fn print_solve_hint(puzzle: PuzzleId) {
+    if default_year() == Some(puzzle.year) {
+        println!("🎄 Type `cargo solve {}` to run your solution.", puzzle.day);
+    } else {
+        println!(
+            "🎄 Type `cargo solve {} --year {}` to run your solution.",
+            puzzle.day, puzzle.year
+        );
+    }
 }
@@ -1,25 +1,94 @@
-use std::process::{Command, Stdio};
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::{self, Command, Stdio};
 
-use crate::Day;
+use crate::template::input::InputSource;
+use crate::template::record::OutputFormat;
+use crate::template::runner::BenchOptions;
+use crate::template::visualize::Visualize;
+use crate::template::watch::Snapshot;
+use crate::template::{ANSI_BOLD, ANSI_RED, ANSI_RESET};
+use crate::PuzzleId;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Clears the terminal and moves the cursor to the top left corner.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
 
-    if release {
-        cmd_args.push("--release".to_string());
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    time: Option<BenchOptions>,
+    submit_part: Option<u8>,
+    format: OutputFormat,
+    input: Option<InputSource>,
+    watch: bool,
+    visualize: Option<Visualize>,
+    alloc_stats: bool,
+) {
+    let mut solution_args = vec![];
+
+    if let Some(submit_part) = submit_part {
+        if watch {
+            eprintln!("`--submit` cannot be combined with `--watch`.");
+            process::exit(1);
+        }
+        solution_args.push("--submit".to_string());
+        solution_args.push(submit_part.to_string());
     }
 
-    cmd_args.push("--".to_string());
+    if let Some(options) = &time {
+        solution_args.push("--time".to_string());
+        solution_args.extend(options.to_args());
+    }
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+    if format != OutputFormat::Text {
+        solution_args.push("--format".to_string());
+        solution_args.push(format.to_string());
+    }
+
+    if let Some(input) = &input {
+        // stdin is used up by the first run, every later run of a watch would read an empty input.
+        if watch && *input == InputSource::Stdin {
+            eprintln!("`--input -` cannot be combined with `--watch`.");
+            process::exit(1);
+        }
+        solution_args.push("--input".to_string());
+        solution_args.push(input.to_string());
+    }
+
+    let mut features = vec![];
+    if alloc_stats {
+        features.push("alloc_stats");
     }
 
-    if time {
-        cmd_args.push("--time".to_string());
+    if let Some(visualize) = &visualize {
+        // recording frames would distort timings, and a playback would block the next run of a watch.
+        if time.is_some() {
+            eprintln!("`--visualize` cannot be combined with `--time`.");
+            process::exit(1);
+        }
+        if watch && *visualize == Visualize::Terminal {
+            eprintln!(
+                "`--visualize` cannot be combined with `--watch`, use `--visualize-dir` instead."
+            );
+            process::exit(1);
+        }
+        solution_args.extend(visualize.to_args());
+        features.push("visualize");
     }
 
+    if watch {
+        let input_file = match &input {
+            Some(InputSource::File(path)) => Some(path.as_path()),
+            _ => None,
+        };
+        watch_solution(puzzle, release, &features, input_file, &solution_args);
+    }
+
+    let mut cmd_args = cargo_args("run", puzzle, release, &features);
+    cmd_args.push("--".to_string());
+    cmd_args.extend(solution_args);
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -29,3 +98,95 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+fn cargo_args(command: &str, puzzle: PuzzleId, release: bool, features: &[&str]) -> Vec<String> {
+    let mut cmd_args = vec![command.to_string(), "--bin".to_string(), puzzle.bin_name()];
+
+    if release {
+        cmd_args.push("--release".to_string());
+    }
+
+    if !features.is_empty() {
+        cmd_args.push("--features".to_string());
+        cmd_args.push(features.join(","));
+    }
+
+    cmd_args
+}
+
+/// Rebuilds and re-runs a solution whenever one of its files changes, until the process is interrupted.
+/// If a build fails, the output of the last successful run stays on screen above the compiler errors.
+fn watch_solution(
+    puzzle: PuzzleId,
+    release: bool,
+    features: &[&str],
+    input_file: Option<&Path>,
+    solution_args: &[String],
+) -> ! {
+    let mut last_output = String::new();
+
+    loop {
+        let snapshot = Snapshot::take(puzzle, input_file);
+
+        let mut build_args = cargo_args("build", puzzle, release, features);
+        build_args.extend(["--quiet".into(), "--color".into(), "always".into()]);
+
+        let build = Command::new("cargo")
+            .args(&build_args)
+            .stderr(Stdio::piped())
+            .output()
+            .unwrap();
+
+        print!("{CLEAR_SCREEN}");
+
+        if build.status.success() {
+            let _ = io::stderr().write_all(&build.stderr);
+
+            let mut run_args = cargo_args("run", puzzle, release, features);
+            run_args.push("--quiet".into());
+            run_args.push("--".into());
+            run_args.extend(solution_args.iter().cloned());
+
+            last_output = run_streamed(&run_args);
+        } else {
+            print!("{last_output}");
+            println!(
+                "{ANSI_RED}{ANSI_BOLD}Build failed, showing the last successful run.{ANSI_RESET}"
+            );
+            let _ = io::stderr().write_all(&build.stderr);
+        }
+
+        println!("---");
+        println!("👀 Watching for changes, press Ctrl-C to stop.");
+        let _ = io::stdout().flush();
+
+        snapshot.wait_for_change(puzzle, input_file);
+    }
+}
+
+/// Runs cargo with the given arguments, printing its output as it arrives. Returns the printed output.
+fn run_streamed(args: &[String]) -> String {
+    let mut child = Command::new("cargo")
+        .args(args)
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut output = vec![];
+    let mut buf = [0; 1024];
+
+    if let Some(mut child_stdout) = child.stdout.take() {
+        let mut stdout = io::stdout();
+        while let Ok(n) = child_stdout.read(&mut buf) {
+            if n == 0 {
+                break;
+            }
+            let _ = stdout.write_all(&buf[..n]);
+            let _ = stdout.flush();
+            output.extend_from_slice(&buf[..n]);
+        }
+    }
+
+    let _ = child.wait();
+    String::from_utf8_lossy(&output).into()
+}
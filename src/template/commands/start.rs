@@ -0,0 +1,68 @@
+use std::{fs, path::Path, process};
+
+use once_cell::unsync::OnceCell;
+
+use crate::template::aoc_client::{self, AocClient};
+use crate::template::commands::scaffold;
+use crate::template::{examples, get_data_path, is_missing_or_empty};
+use crate::PuzzleId;
+
+/// Sets up a day in one go: downloads its input and puzzle, extracts the examples, scaffolds the module and prints the puzzle.
+/// Steps whose files already exist are skipped, so re-running the command only fills in what is missing. The puzzle is
+/// downloaded again until it contains part two.
+pub fn handle(puzzle: PuzzleId, template: Option<&str>) {
+    if let Err(e) = start(puzzle, template) {
+        eprintln!("failed to start puzzle: {e}");
+        process::exit(1);
+    }
+}
+
+fn start(puzzle: PuzzleId, template: Option<&str>) -> Result<(), String> {
+    // the client needs a session, which is only required once something is actually downloaded.
+    let client = OnceCell::new();
+    let client = || client.get_or_try_init(|| AocClient::from_env().map_err(|e| e.to_string()));
+
+    let input_path = get_data_path("inputs", puzzle, "txt");
+    if is_missing_or_empty(&input_path).map_err(|e| e.to_string())? {
+        let input = client()?.get_input(puzzle).map_err(|e| e.to_string())?;
+        aoc_client::write_file(&input_path, &input).map_err(|e| e.to_string())?;
+        println!("🎄 Successfully wrote input to \"{input_path}\".");
+    }
+
+    let puzzle_path = get_data_path("puzzles", puzzle, "md");
+    let description = match fs::read_to_string(&puzzle_path) {
+        Ok(description) if has_part_two(&description) => description,
+        _ => {
+            let description = client()?.get_puzzle(puzzle).map_err(|e| e.to_string())?;
+            aoc_client::write_file(&puzzle_path, &description).map_err(|e| e.to_string())?;
+            println!("🎄 Successfully wrote puzzle to \"{puzzle_path}\".");
+            description
+        }
+    };
+
+    for (i, example) in examples::extract(&description).iter().enumerate() {
+        for path in examples::write(puzzle, i + 1, example, false).map_err(|e| e.to_string())? {
+            println!("🎄 Successfully wrote example to \"{path}\".");
+        }
+    }
+
+    if !Path::new(&scaffold::get_module_path(puzzle)).exists() {
+        scaffold::create_module(puzzle, template)?;
+    }
+    scaffold::create_examples(puzzle, 1)?;
+
+    println!("---");
+    println!("{description}");
+    println!("---");
+    scaffold::print_solve_hint(puzzle);
+    Ok(())
+}
+
+/// Whether a downloaded puzzle already contains the second part, which is unlocked after solving the first one.
+fn has_part_two(description: &str) -> bool {
+    description
+        .lines()
+        .filter(|line| examples::is_heading(line))
+        .count()
+        > 1
+}
@@ -0,0 +1,261 @@
+use std::{
+    io,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use crate::template::{
+    answers, commands::all::get_path_for_input, examples, is_missing_or_empty,
+    readme_benchmarks::get_path_for_bin, submissions, ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC, ANSI_RED,
+    ANSI_RESET,
+};
+use crate::{all_days, Day, PuzzleId};
+
+/// The outcome of the tests of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tests {
+    /// The day is not scaffolded, or tests were skipped.
+    NotRun,
+    Passed,
+    Failed,
+}
+
+/// The progress on one part of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part {
+    /// Nothing was submitted yet.
+    Open,
+    /// Wrong answers were submitted, but none was correct yet.
+    Attempted(usize),
+    /// The part has a confirmed answer.
+    Solved,
+}
+
+/// What is known about a day, gathered from the files of the project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DayStatus {
+    day: Day,
+    scaffolded: bool,
+    input: bool,
+    examples: usize,
+    tests: Tests,
+    parts: [Part; 2],
+}
+
+/// Prints a matrix of the progress of every day of `year`: whether it is scaffolded, has an input and examples,
+/// passes its tests and has confirmed answers. The tests of scaffolded days are run with `cargo test`, unless `run_tests` is unset.
+pub fn handle(year: u16, run_tests: bool) {
+    if run_tests {
+        eprintln!("{ANSI_ITALIC}Running tests...{ANSI_RESET}");
+        build_tests();
+    }
+
+    let days: Vec<DayStatus> = all_days()
+        .map(|day| match read_status(PuzzleId { year, day }, run_tests) {
+            Ok(status) => status,
+            Err(e) => {
+                eprintln!("Day {day}: could not read status: {e}");
+                std::process::exit(1);
+            }
+        })
+        .collect();
+
+    println!("{ANSI_BOLD}Day  Code  Input  Examples  Tests  Part 1  Part 2{ANSI_RESET}");
+    for status in &days {
+        println!("{}", format_row(status));
+    }
+    println!("---");
+    println!("{}", format_summary(&days));
+}
+
+fn read_status(puzzle: PuzzleId, run_tests: bool) -> Result<DayStatus, io::Error> {
+    let scaffolded = Path::new(&get_path_for_bin(puzzle)).exists();
+
+    let examples = (1..)
+        .take_while(|&index| Path::new(&examples::get_path(puzzle, index, "txt")).exists())
+        .count();
+
+    let tests = if scaffolded && run_tests {
+        run_tests_of(puzzle)
+    } else {
+        Tests::NotRun
+    };
+
+    let confirmed = answers::read(puzzle)?;
+    let submissions = submissions::read(puzzle)?;
+    let part = |part: u8| {
+        if confirmed.get(part).is_some() {
+            return Part::Solved;
+        }
+        match submissions
+            .iter()
+            .filter(|x| x.part == part)
+            .filter(|x| x.verdict.is_wrong())
+            .count()
+        {
+            0 => Part::Open,
+            attempts => Part::Attempted(attempts),
+        }
+    };
+
+    Ok(DayStatus {
+        day: puzzle.day,
+        scaffolded,
+        input: !is_missing_or_empty(&get_path_for_input(puzzle))?,
+        examples,
+        tests,
+        parts: [part(1), part(2)],
+    })
+}
+
+/// Compiles the tests of all solutions up front, so they are not built one by one.
+fn build_tests() {
+    let _ = Command::new("cargo")
+        .args(["test", "--bins", "--no-run", "--quiet"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+fn run_tests_of(puzzle: PuzzleId) -> Tests {
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &puzzle.bin_name()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+
+    match status {
+        Ok(status) if status.success() => Tests::Passed,
+        _ => Tests::Failed,
+    }
+}
+
+/// Pads a cell to its column, coloring its text.
+fn cell(text: &str, width: usize, color: &str) -> String {
+    if color.is_empty() {
+        format!("{text:<width$}")
+    } else {
+        format!("{color}{text:<width$}{ANSI_RESET}")
+    }
+}
+
+fn check(value: bool, width: usize) -> String {
+    if value {
+        cell("✔", width, ANSI_GREEN)
+    } else {
+        cell("-", width, "")
+    }
+}
+
+fn format_row(status: &DayStatus) -> String {
+    let examples = match status.examples {
+        0 => cell("-", 10, ""),
+        count => cell(&count.to_string(), 10, ""),
+    };
+
+    let tests = match status.tests {
+        Tests::NotRun => cell("-", 7, ""),
+        Tests::Passed => cell("✔", 7, ANSI_GREEN),
+        Tests::Failed => cell("✖", 7, ANSI_RED),
+    };
+
+    // the last column is not padded, and ⭐ takes up two columns in the terminal.
+    let part = |part: Part, width: usize| match part {
+        Part::Open => cell("-", width, ""),
+        Part::Attempted(attempts) => cell(&format!("✖ {attempts}"), width, ANSI_RED),
+        Part::Solved => cell("⭐", width.saturating_sub(1), ""),
+    };
+
+    format!(
+        "{:>3}  {}{}{examples}{tests}{}{}",
+        status.day.into_inner(),
+        check(status.scaffolded, 6),
+        check(status.input, 7),
+        part(status.parts[0], 8),
+        part(status.parts[1], 0),
+    )
+}
+
+fn format_summary(days: &[DayStatus]) -> String {
+    let stars = days
+        .iter()
+        .flat_map(|status| status.parts)
+        .filter(|part| *part == Part::Solved)
+        .count();
+    let scaffolded = days.iter().filter(|status| status.scaffolded).count();
+    let count_tests = |tests: Tests| days.iter().filter(|x| x.tests == tests).count();
+
+    let mut summary =
+        format!("{ANSI_BOLD}{stars}/50 ⭐{ANSI_RESET} · {scaffolded}/25 days scaffolded");
+
+    match (count_tests(Tests::Passed), count_tests(Tests::Failed)) {
+        (0, 0) => {}
+        (passed, 0) => summary.push_str(&format!(" · {passed} passing")),
+        (passed, failed) => summary.push_str(&format!(
+            " · {passed} passing, {ANSI_RED}{failed} failing{ANSI_RESET}"
+        )),
+    }
+
+    summary
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_row, format_summary, DayStatus, Part, Tests};
+    use crate::day;
+    use crate::template::strip_ansi;
+
+    fn get_mock_days() -> Vec<DayStatus> {
+        vec![
+            DayStatus {
+                day: day!(1),
+                scaffolded: true,
+                input: true,
+                examples: 2,
+                tests: Tests::Passed,
+                parts: [Part::Solved, Part::Solved],
+            },
+            DayStatus {
+                day: day!(2),
+                scaffolded: true,
+                input: true,
+                examples: 1,
+                tests: Tests::Failed,
+                parts: [Part::Solved, Part::Attempted(2)],
+            },
+            DayStatus {
+                day: day!(12),
+                scaffolded: false,
+                input: false,
+                examples: 0,
+                tests: Tests::NotRun,
+                parts: [Part::Open, Part::Open],
+            },
+        ]
+    }
+
+    #[test]
+    fn formats_rows() {
+        let rows: Vec<String> = get_mock_days()
+            .iter()
+            .map(|x| strip_ansi(&format_row(x)))
+            .collect();
+
+        assert_eq!(
+            rows,
+            [
+                "  1  ✔     ✔      2         ✔      ⭐      ⭐",
+                "  2  ✔     ✔      1         ✖      ⭐      ✖ 2",
+                " 12  -     -      -         -      -       -",
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_summary() {
+        assert_eq!(
+            strip_ansi(&format_summary(&get_mock_days())),
+            "3/50 ⭐ · 2/25 days scaffolded · 1 passing, 1 failing"
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use std::{fs, process};
+
+use crate::template::{
+    answers,
+    commands::all::get_path_for_input,
+    error::indent,
+    registry::{self, Solution},
+    ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET,
+};
+use crate::{all_days, PuzzleId};
+
+/// Runs every solved day of `year` with confirmed answers and checks its results.
+/// Exits with a non-zero status if any part does not match its confirmed answer.
+pub fn handle(solutions: &[Solution], year: u16) {
+    let mut checked = 0;
+    let mut failed = 0;
+
+    for day in all_days() {
+        let puzzle = PuzzleId { year, day };
+
+        let Some(solution) = registry::find(solutions, puzzle) else {
+            continue;
+        };
+
+        let answers = match answers::read(puzzle) {
+            Ok(answers) if answers.is_empty() => continue,
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!(
+                    "Day {day}: could not read \"{}\": {e}",
+                    answers::get_path(puzzle)
+                );
+                failed += 1;
+                continue;
+            }
+        };
+
+        let input = match fs::read_to_string(get_path_for_input(puzzle)) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {day}: could not read input file: {e}");
+                failed += 1;
+                continue;
+            }
+        };
+
+        solution.run_catching(&input, None, false, &mut |record| {
+            let part = record.part;
+            let Some(expected) = answers.get(part) else {
+                return;
+            };
+
+            let answer = record.answer.as_deref().unwrap_or("✖");
+            checked += 1;
+
+            if let Some(error) = &record.error {
+                failed += 1;
+                println!(
+                    "Day {day} Part {part}: {ANSI_RED}✖ error{ANSI_RESET} (expected {expected})"
+                );
+                println!("{ANSI_RED}{}{ANSI_RESET}", indent(error));
+            } else if answers.check(part, record.answer.as_deref()) == Some(true) {
+                println!("Day {day} Part {part}: {ANSI_GREEN}✔{ANSI_RESET} {answer}");
+            } else {
+                failed += 1;
+                println!(
+                    "Day {day} Part {part}: {ANSI_RED}✖{ANSI_RESET} {answer} (expected {expected})"
+                );
+            }
+        });
+    }
+
+    println!("---");
+
+    if failed > 0 {
+        println!("{ANSI_BOLD}{failed} check(s) failed, {checked} answer(s) checked.{ANSI_RESET}");
+        process::exit(1);
+    }
+
+    println!("{ANSI_BOLD}All {checked} answer(s) match.{ANSI_RESET}");
+}
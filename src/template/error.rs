@@ -0,0 +1,152 @@
+/// Errors that solution parts can return, and how the runner reports them.
+/// Parts that return `Result<Option<T>, E>` have their error chain printed instead of panicking.
+use std::{error::Error, fmt::Display};
+
+/// A problem with the puzzle input, located by line and optionally column (both starting at 1).
+#[derive(Debug)]
+pub struct InputError {
+    pub line: usize,
+    pub column: Option<usize>,
+    /// The text of the line, shown along with the location when the error is reported.
+    pub text: Option<String>,
+    pub message: String,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl InputError {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column: None,
+            text: None,
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    #[must_use]
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_source(mut self, source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "line {}, column {column}: {}", self.line, self.message),
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|e| e as &(dyn Error + 'static))
+    }
+}
+
+/// Parses every line of `input`. Errors are wrapped into an [`InputError`] that points at the failing line.
+pub fn parse_lines<T, E>(
+    input: &str,
+    mut parse: impl FnMut(&str) -> Result<T, E>,
+) -> Result<Vec<T>, InputError>
+where
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            parse(line).map_err(|e| {
+                InputError::new(i + 1, "could not parse line")
+                    .with_text(line)
+                    .with_source(e)
+            })
+        })
+        .collect()
+}
+
+/// Formats an error along with its chain of sources, one per line.
+/// Input errors that know the text of their line are followed by the line, with a marker below the column if known.
+#[must_use]
+pub fn format_error(error: &(dyn Error + 'static)) -> String {
+    let mut lines = vec![];
+    let mut current = Some(error);
+
+    while let Some(e) = current {
+        if lines.is_empty() {
+            lines.push(e.to_string());
+        } else {
+            lines.push(format!("caused by: {e}"));
+        }
+
+        if let Some(InputError {
+            text: Some(text),
+            column,
+            ..
+        }) = e.downcast_ref::<InputError>()
+        {
+            lines.push(format!("  | {text}"));
+            if let Some(column) = column {
+                lines.push(format!("  | {}^", " ".repeat(column.saturating_sub(1))));
+            }
+        }
+
+        current = e.source();
+    }
+
+    lines.join("\n")
+}
+
+/// Indents every line of a formatted error, to set it apart from the surrounding output.
+pub(crate) fn indent(s: &str) -> String {
+    s.lines()
+        .map(|line| format!("  {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_error, parse_lines, InputError};
+
+    #[test]
+    fn formats_input_errors() {
+        let error = InputError::new(3, "unknown operation")
+            .with_column(4)
+            .with_text("rn=1,cm-")
+            .with_source("expected `=` or `-`");
+        assert_eq!(
+            format_error(&error),
+            "line 3, column 4: unknown operation\n  | rn=1,cm-\n  |    ^\ncaused by: expected `=` or `-`"
+        );
+    }
+
+    #[test]
+    fn parses_lines() {
+        assert_eq!(
+            parse_lines("1\n2\n3", str::parse::<u32>).unwrap(),
+            vec![1, 2, 3]
+        );
+
+        let error = parse_lines("1\nx\n3", str::parse::<u32>).unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(
+            format_error(&error),
+            "line 2: could not parse line\n  | x\ncaused by: invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,305 @@
+/// Extracts the examples of a puzzle, and the answers they are expected to produce, from the puzzle markdown.
+/// The first example of a day is stored in `data/{year}/examples/{day}.txt`, further examples in `{day}-2.txt`, `{day}-3.txt`...
+/// The expected answers of an example are stored next to it, e.g. in `{day}.answers`, in the format of the confirmed answers.
+use std::{fs, io, path::Path};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::template::answers::{self, Answers};
+use crate::template::runner::PartOutput;
+use crate::template::{get_data_path_part, is_missing_or_empty};
+use crate::PuzzleId;
+
+/// An example input along with the answers that the puzzle text highlights for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answers: Answers,
+}
+
+/// Finds the examples in the markdown of a puzzle.
+///
+/// Code blocks count as examples if the text leading up to them mentions an example, which skips intermediate states
+/// that many puzzles print. Blocks that quote a part of an earlier example, e.g. to explain one line of it, are skipped
+/// as well. The last highlighted value (`` `*42*` ``) of a part is taken as its expected answer, and
+/// belongs to the last example before it.
+#[must_use]
+pub fn extract(markdown: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+    let mut part = 0;
+    let mut answer: Option<(String, usize)> = None;
+    let mut previous_text = "";
+    let mut lines = markdown.lines();
+
+    while let Some(line) = lines.next() {
+        if is_heading(line) {
+            set_answer(&mut examples, part, answer.take());
+            part += 1;
+        } else if line.starts_with("```") {
+            let block: Vec<&str> = lines
+                .by_ref()
+                .take_while(|x| !x.starts_with("```"))
+                .collect();
+
+            if previous_text.to_ascii_lowercase().contains("example")
+                && !is_quoted(&examples, &block)
+            {
+                examples.push(Example {
+                    input: block.iter().map(|x| format!("{x}\n")).collect(),
+                    answers: Answers::default(),
+                });
+            }
+        } else if !line.trim().is_empty() {
+            previous_text = line;
+
+            if let (Some(value), Some(index)) =
+                (last_highlighted(line), examples.len().checked_sub(1))
+            {
+                answer = Some((value, index));
+            }
+        }
+    }
+
+    set_answer(&mut examples, part, answer);
+    examples
+}
+
+/// Whether a code block repeats lines of an earlier example.
+fn is_quoted(examples: &[Example], block: &[&str]) -> bool {
+    let text = block.join("\n");
+    let text = text.trim();
+    !text.is_empty() && examples.iter().any(|example| example.input.contains(text))
+}
+
+/// Whether a line is the heading of a part, e.g. `## --- Part Two ---`. Older downloads escape the heading instead.
+pub(crate) fn is_heading(line: &str) -> bool {
+    let title = line.trim_start_matches("## ").trim_start_matches('\\');
+    title.starts_with("--- ") && title.ends_with(" ---")
+}
+
+fn set_answer(examples: &mut [Example], part: u8, answer: Option<(String, usize)>) {
+    if let Some((value, index)) = answer {
+        examples[index].answers.set(part, &value);
+    }
+}
+
+/// Returns the last value of a line that is highlighted as code and emphasized, e.g. `` `*42*` `` or ``*`42`*``.
+fn last_highlighted(line: &str) -> Option<String> {
+    static HIGHLIGHTED: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"`\*([^`*]+)\*`|\*`([^`*]+)`\*").unwrap());
+
+    HIGHLIGHTED
+        .captures_iter(line)
+        .last()
+        .and_then(|captures| captures.get(1).or_else(|| captures.get(2)))
+        .map(|value| value.as_str().to_string())
+}
+
+/// Returns the path of the n-th example file of a puzzle (starting from 1).
+#[must_use]
+pub fn get_path(puzzle: PuzzleId, index: usize, extension: &str) -> String {
+    get_data_path_part("examples", puzzle, index, extension)
+}
+
+/// Reads the expected answers of the n-th example of a puzzle. A missing file means that no answers are known.
+pub fn read_answers(puzzle: PuzzleId, index: usize) -> Result<Answers, io::Error> {
+    answers::read_path(&get_path(puzzle, index, "answers"))
+}
+
+/// Reads the numbered examples of a puzzle along with their expected answers, up to the first missing example file.
+pub fn read_all(puzzle: PuzzleId) -> Result<Vec<Example>, io::Error> {
+    let mut examples = vec![];
+
+    for index in 1.. {
+        let path = get_path(puzzle, index, "txt");
+        if !Path::new(&path).exists() {
+            break;
+        }
+        examples.push(Example {
+            input: fs::read_to_string(path)?,
+            answers: read_answers(puzzle, index)?,
+        });
+    }
+
+    Ok(examples)
+}
+
+/// Runs a solution part on every example that has an expected answer for it, and panics if an answer does not match.
+/// Meant to be called from the tests of a solution, e.g. `check(PUZZLE, 1, part_one)`.
+pub fn check<R: PartOutput>(puzzle: PuzzleId, part: u8, func: impl Fn(&str) -> R) {
+    let examples = read_all(puzzle).expect("could not read examples");
+
+    for (i, example) in examples.iter().enumerate() {
+        let Some(expected) = example.answers.get(part) else {
+            continue;
+        };
+
+        let path = get_path(puzzle, i + 1, "txt");
+        let answer = func(&example.input)
+            .into_answer()
+            .unwrap_or_else(|e| panic!("part {part} failed on \"{path}\": {e}"));
+
+        assert_eq!(
+            answer.as_deref(),
+            Some(expected),
+            "wrong answer for part {part} on \"{path}\""
+        );
+    }
+}
+
+/// Writes an example and its expected answers. Existing files that are not empty are kept, unless `force` is set.
+/// Returns the paths of the files that were written.
+pub fn write(
+    puzzle: PuzzleId,
+    index: usize,
+    example: &Example,
+    force: bool,
+) -> Result<Vec<String>, io::Error> {
+    let mut written = vec![];
+
+    let input_path = get_path(puzzle, index, "txt");
+    if force || is_missing_or_empty(&input_path)? {
+        if let Some(parent) = Path::new(&input_path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&input_path, &example.input)?;
+        written.push(input_path);
+    }
+
+    let answers_path = get_path(puzzle, index, "answers");
+    let mut answers = answers::read_path(&answers_path)?;
+    let mut changed = false;
+
+    for part in [1, 2] {
+        if let Some(value) = example.answers.get(part) {
+            if force || answers.get(part).is_none() {
+                changed |= answers.get(part) != Some(value);
+                answers.set(part, value);
+            }
+        }
+    }
+
+    if changed {
+        answers::write_path(&answers_path, &answers)?;
+        written.push(answers_path);
+    }
+
+    Ok(written)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, get_path, is_heading, last_highlighted};
+    use crate::puzzle;
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+The newly-improved calibration document consists of lines of text. For example:
+
+```
+1abc2
+treb7uchet
+```
+
+In this example, the calibration values of these lines are `12` and `77`. Adding these together produces `*89*`.
+
+What is the *sum* of all of the calibration values?
+
+## --- Part Two ---
+
+It looks like some of the digits are actually spelled out. For example:
+
+```
+two1nine
+```
+
+After the first step, the document looks like this:
+
+```
+219
+```
+
+In this example, the calibration value is `*29*`.
+";
+
+    #[test]
+    fn extracts_examples() {
+        let examples = extract(PUZZLE);
+        assert_eq!(examples.len(), 2);
+
+        assert_eq!(examples[0].input, "1abc2\ntreb7uchet\n");
+        assert_eq!(examples[0].answers.get(1), Some("89"));
+        assert_eq!(examples[0].answers.get(2), None);
+
+        assert_eq!(examples[1].input, "two1nine\n");
+        assert_eq!(examples[1].answers.get(1), None);
+        assert_eq!(examples[1].answers.get(2), Some("29"));
+    }
+
+    #[test]
+    fn skips_quoted_fragments() {
+        let puzzle = "## --- Day 5: If You Give A Seed A Fertilizer ---
+
+For example:
+
+```
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+```
+
+Consider again the example `seed-to-soil map`:
+
+```
+50 98 2
+52 50 48
+
+```
+
+So, the lowest location number in this example is `*35*`.
+
+## --- Part Two ---
+
+In the above example, the lowest location number can be obtained from seed number `82`. So, the lowest location number is `*46*`.
+";
+
+        let examples = extract(puzzle);
+        assert_eq!(examples.len(), 1);
+        assert!(examples[0].input.starts_with("seeds: 79 14 55 13\n"));
+        assert_eq!(examples[0].answers.get(1), Some("35"));
+        assert_eq!(examples[0].answers.get(2), Some("46"));
+    }
+
+    #[test]
+    fn detects_part_headings() {
+        assert!(is_heading("## --- Part Two ---"));
+        assert!(is_heading("\\--- Day 1: Trebuchet?! ---"));
+        assert!(!is_heading("----------"));
+        assert!(!is_heading("--- not a heading"));
+    }
+
+    #[test]
+    fn finds_highlighted_values() {
+        assert_eq!(
+            last_highlighted("`*12*` and *`77`*, total `100`"),
+            Some("77".into())
+        );
+        assert_eq!(last_highlighted("the *sum* of `12`"), None);
+    }
+
+    #[test]
+    fn numbers_example_paths() {
+        let puzzle = puzzle!(2023, 8);
+        assert_eq!(get_path(puzzle, 1, "txt"), "data/2023/examples/08.txt");
+        assert_eq!(
+            get_path(puzzle, 3, "answers"),
+            "data/2023/examples/08-3.answers"
+        );
+    }
+}
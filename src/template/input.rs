@@ -0,0 +1,154 @@
+/// Selects the input a solution binary runs on, passed as `--input <source>`.
+/// Answers are only verified and submitted for the real puzzle input.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    process,
+    str::FromStr,
+};
+
+use crate::template::get_data_path;
+use crate::PuzzleId;
+
+/// The data folder that holds the real puzzle inputs.
+const PUZZLE_INPUTS: &str = "inputs";
+
+/// Where the input of a solution run is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// A data folder of the puzzle year, e.g. `inputs` or `examples`.
+    Folder(String),
+    /// A file at any path.
+    File(PathBuf),
+    /// The standard input, selected with `-`.
+    Stdin,
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        InputSource::Folder(PUZZLE_INPUTS.into())
+    }
+}
+
+impl InputSource {
+    /// Reads the `--input` argument passed to a solution binary, defaulting to the puzzle input.
+    #[must_use]
+    pub fn from_args(args: &[String]) -> Self {
+        args.iter()
+            .position(|x| x == "--input")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Whether the source is the real puzzle input, which the confirmed and submitted answers belong to.
+    #[must_use]
+    pub fn is_puzzle_input(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Reads the input of a puzzle from this source.
+    pub fn read(&self, puzzle: PuzzleId) -> Result<String, io::Error> {
+        match self {
+            InputSource::Folder(folder) => fs::read_to_string(get_data_path(folder, puzzle, "txt")),
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+
+    fn describe(&self, puzzle: PuzzleId) -> String {
+        match self {
+            InputSource::Folder(folder) => format!("\"{}\"", get_data_path(folder, puzzle, "txt")),
+            InputSource::File(path) => format!("\"{}\"", path.display()),
+            InputSource::Stdin => "stdin".into(),
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = ParseInputSourceError;
+
+    /// `-` selects stdin, plain names like `examples` select a data folder and anything else is a path.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            Err(ParseInputSourceError)
+        } else if s == "-" {
+            Ok(InputSource::Stdin)
+        } else if s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            Ok(InputSource::Folder(s.into()))
+        } else {
+            Ok(InputSource::File(s.into()))
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Folder(folder) => f.write_str(folder),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => f.write_str("-"),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`InputSource`].
+#[derive(Debug)]
+pub struct ParseInputSourceError;
+
+impl std::error::Error for ParseInputSourceError {}
+
+impl Display for ParseInputSourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a data folder like `examples`, a file path or `-` for stdin")
+    }
+}
+
+/// Reads the input selected by the arguments of a solution binary. Exits if the input cannot be read.
+#[must_use]
+pub fn read_input(puzzle: PuzzleId) -> String {
+    let args: Vec<String> = env::args().collect();
+    let source = InputSource::from_args(&args);
+
+    source.read(puzzle).unwrap_or_else(|e| {
+        eprintln!("Could not read input from {}: {e}", source.describe(puzzle));
+        process::exit(1);
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+
+    #[test]
+    fn parses_input_sources() {
+        let parse = |s: &str| s.parse::<InputSource>().unwrap();
+
+        assert_eq!(parse("examples"), InputSource::Folder("examples".into()));
+        assert_eq!(parse("-"), InputSource::Stdin);
+        assert_eq!(
+            parse("path/to/file.txt"),
+            InputSource::File("path/to/file.txt".into())
+        );
+        assert_eq!(parse("input.txt"), InputSource::File("input.txt".into()));
+        assert!("".parse::<InputSource>().is_err());
+
+        assert!(parse("inputs").is_puzzle_input());
+        assert!(!parse("examples").is_puzzle_input());
+        assert_eq!(parse("path/to/file.txt").to_string(), "path/to/file.txt");
+    }
+
+    #[test]
+    fn reads_input_argument() {
+        let args: Vec<String> = ["2023_05", "--input", "-"].map(Into::into).to_vec();
+        assert_eq!(InputSource::from_args(&args), InputSource::Stdin);
+        assert_eq!(InputSource::from_args(&[]), InputSource::default());
+    }
+}
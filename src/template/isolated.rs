@@ -0,0 +1,310 @@
+/// Runs the solution of a day in a child process, so it can be stopped once it exceeds its [`Limits`].
+/// The child is the main binary itself, started with the internal `run-day` command. It runs the day through the registry
+/// and reports one JSON [`Record`] per part on stdout.
+use std::{
+    env, fs,
+    io::{stdout, BufRead, BufReader, Read, Write},
+    process::{self, Command, Stdio},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::template::{
+    commands::all::get_path_for_input,
+    limits::{self, Limits},
+    record::{part_label, Record, Status},
+    registry::{self, Solution},
+    runner::BenchOptions,
+};
+use crate::PuzzleId;
+
+/// How often a running day is checked against its limits.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Runs a day in a child process. Of the `parts` a run of the day reports, the ones that did not finish because the child
+/// was stopped or crashed are returned as records with the status and reason.
+/// The timeout applies to each part on its own. Timed runs first execute every part once, see [`serve`]: only these
+/// executions are held to the timeout, so benchmarking a part that is fast enough is never stopped.
+#[must_use]
+pub fn run_day(
+    puzzle: PuzzleId,
+    parts: &[u8],
+    time: Option<&BenchOptions>,
+    limits: &Limits,
+) -> Vec<Record> {
+    let mut args = vec![
+        "run-day".to_string(),
+        puzzle.day.to_string(),
+        "--year".to_string(),
+        puzzle.year.to_string(),
+    ];
+
+    if let Some(options) = time {
+        args.push("--time".into());
+        args.extend(options.to_args());
+    }
+
+    let child = env::current_exe().and_then(|exe| {
+        Command::new(exe)
+            .args(&args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+    });
+
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            return fill_missing(
+                puzzle,
+                parts,
+                vec![],
+                Status::Error,
+                &format!("could not start run: {e}"),
+                Duration::ZERO,
+            )
+        }
+    };
+
+    let (tx, rx) = mpsc::channel();
+
+    if let Some(child_stdout) = child.stdout.take() {
+        thread::spawn(move || {
+            for line in BufReader::new(child_stdout).lines().map_while(Result::ok) {
+                if let Some(record) = Record::from_json(&line) {
+                    if tx.send(record).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    let stderr_reader = child.stderr.take().map(|mut child_stderr| {
+        thread::spawn(move || {
+            let mut output = String::new();
+            let _ = child_stderr.read_to_string(&mut output);
+            output
+        })
+    });
+
+    let mut part_started = Instant::now();
+    let mut records = vec![];
+    let mut checks = vec![];
+    let mut checks_left = if time.is_some() { parts.len() } else { 0 };
+
+    // stdout is closed once the child exits, until then the limits are checked between records.
+    let stopped = loop {
+        match rx.recv_timeout(POLL_INTERVAL) {
+            Ok(record) if checks_left > 0 => {
+                checks.push(record);
+                checks_left -= 1;
+                part_started = Instant::now();
+                continue;
+            }
+            Ok(record) => {
+                records.push(record);
+                part_started = Instant::now();
+                continue;
+            }
+            Err(RecvTimeoutError::Disconnected) => break None,
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+
+        let is_benching = time.is_some() && checks_left == 0;
+
+        if !is_benching
+            && limits
+                .timeout
+                .is_some_and(|timeout| part_started.elapsed() > timeout)
+        {
+            break Some(Status::Timeout);
+        }
+
+        if limits.memory.is_some_and(|memory| {
+            limits::resident_memory(child.id()).is_some_and(|resident| resident > memory)
+        }) {
+            break Some(Status::OutOfMemory);
+        }
+    };
+
+    let part_duration = part_started.elapsed();
+
+    // a day stopped before benching started reports the parts that finished their single execution.
+    if checks_left > 0 {
+        records = checks;
+    }
+
+    if stopped.is_some() {
+        let _ = child.kill();
+    }
+
+    let exit_status = child.wait();
+    let stderr = stderr_reader
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default();
+
+    match stopped {
+        Some(status) => fill_missing(
+            puzzle,
+            parts,
+            records,
+            status,
+            &limits::describe(limits, status == Status::Timeout),
+            part_duration,
+        ),
+        None => {
+            let exit_status = match exit_status {
+                Ok(exit_status) => exit_status.to_string(),
+                Err(e) => e.to_string(),
+            };
+            let reason = match stderr.trim() {
+                "" => format!("run failed with {exit_status}"),
+                stderr => format!("run failed with {exit_status}\n{stderr}"),
+            };
+            fill_missing(
+                puzzle,
+                parts,
+                records,
+                Status::Error,
+                &reason,
+                part_duration,
+            )
+        }
+    }
+}
+
+/// Adds a record for each part that did not report a result. Only the first of them was running, it gets the `duration`.
+/// Later parts did not run at all and are recorded without samples.
+pub(crate) fn fill_missing(
+    puzzle: PuzzleId,
+    parts: &[u8],
+    mut records: Vec<Record>,
+    status: Status,
+    reason: &str,
+    duration: Duration,
+) -> Vec<Record> {
+    let mut running = None;
+
+    for &part in parts {
+        if records.iter().any(|record| record.part == part) {
+            continue;
+        }
+
+        let (duration, samples, error) = match running {
+            None => {
+                running = Some(part);
+                (duration, 1, reason.to_string())
+            }
+            Some(running) => (
+                Duration::ZERO,
+                0,
+                format!("not run, {} {reason}", part_label(running).to_lowercase()),
+            ),
+        };
+
+        records.push(Record {
+            puzzle,
+            part,
+            answer: None,
+            duration,
+            samples,
+            status,
+            stats: None,
+            verified: None,
+            error: Some(error),
+            memory: None,
+        });
+    }
+
+    records.sort_by_key(|record| record.part);
+    records
+}
+
+/// Entry point of the child process: runs a day and prints the records of its parts as JSON lines.
+/// Timed runs execute every part once before benchmarking it, so the parent can apply the timeout to single executions.
+pub fn serve(solutions: &[Solution], puzzle: PuzzleId, time: Option<BenchOptions>) {
+    let Some(solution) = registry::find(solutions, puzzle) else {
+        eprintln!("Day {} is not solved.", puzzle.day);
+        process::exit(1);
+    };
+
+    let input = fs::read_to_string(get_path_for_input(puzzle)).unwrap_or_else(|e| {
+        eprintln!("Could not read input file: {e}");
+        process::exit(1);
+    });
+
+    let mut emit = |record: Record| {
+        println!("{}", record.to_json());
+        let _ = stdout().flush();
+    };
+
+    if time.is_some() {
+        (solution.run)(&input, None, false, &mut emit);
+    }
+
+    (solution.run)(&input, time.as_ref(), false, &mut emit);
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::fill_missing;
+    use crate::puzzle;
+    use crate::template::record::{Record, Status, PARSE};
+
+    #[test]
+    fn fills_parts_that_did_not_finish() {
+        let puzzle = puzzle!(2023, 14);
+        let part_one = Record {
+            puzzle,
+            part: 1,
+            answer: Some("42".into()),
+            duration: Duration::from_millis(3),
+            samples: 1,
+            status: Status::Solved,
+            stats: None,
+            verified: None,
+            error: None,
+            memory: None,
+        };
+
+        let records = fill_missing(
+            puzzle,
+            &[1, 2],
+            vec![part_one.clone()],
+            Status::Timeout,
+            "exceeded the time limit of 1s",
+            Duration::from_secs(1),
+        );
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0], part_one);
+        assert_eq!(records[1].status, Status::Timeout);
+        assert_eq!(records[1].duration, Duration::from_secs(1));
+        assert_eq!(
+            records[1].error.as_deref(),
+            Some("exceeded the time limit of 1s")
+        );
+
+        let records = fill_missing(
+            puzzle,
+            &[PARSE, 1, 2],
+            vec![],
+            Status::Timeout,
+            "exceeded the time limit of 1s",
+            Duration::from_secs(1),
+        );
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].part, PARSE);
+        assert_eq!(records[0].duration, Duration::from_secs(1));
+        assert_eq!(
+            records[2].error.as_deref(),
+            Some("not run, parse exceeded the time limit of 1s")
+        );
+    }
+}
@@ -0,0 +1,325 @@
+/// Private leaderboards, as served by `/{year}/leaderboard/private/view/{id}.json`.
+/// The id of a private leaderboard is the user id of its owner, which is read from the `AOC_USER_ID` environment variable.
+/// Downloaded leaderboards are cached in `data/{year}/leaderboard.json`, since the API should not be polled more than once every 15 minutes.
+use std::{
+    collections::BTreeMap,
+    collections::HashMap,
+    env, fs,
+    time::{Duration, SystemTime},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::{self, AocClient};
+use crate::Day;
+
+/// How long a downloaded leaderboard is reused before it is downloaded again.
+const CACHE_DURATION: Duration = Duration::from_secs(15 * 60);
+
+/// Puzzles unlock at midnight EST, which is five hours after midnight UTC.
+const UNLOCK_OFFSET_SECS: u64 = 5 * 60 * 60;
+
+/// The progress of the members of a private leaderboard in one year.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub year: u16,
+    pub owner_id: u64,
+    pub members: Vec<Member>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    /// Anonymous members have no name.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    /// When the stars of each day were earned.
+    pub days: BTreeMap<Day, Completion>,
+}
+
+/// Unix timestamps of the stars of a day, [`None`] for parts that are not solved yet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Completion {
+    pub part_1: Option<u64>,
+    pub part_2: Option<u64>,
+}
+
+impl Leaderboard {
+    /// Parses the JSON of a private leaderboard.
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let value: JsonValue = json
+            .parse()
+            .map_err(|e| format!("invalid leaderboard JSON: {e}"))?;
+
+        let object: &HashMap<String, JsonValue> = value
+            .get()
+            .ok_or("expected the leaderboard to be an object")?;
+
+        let year = match object.get("event") {
+            Some(JsonValue::String(event)) => event.parse().ok(),
+            Some(JsonValue::Number(event)) => to_integer(*event),
+            _ => None,
+        }
+        .ok_or("missing `event` of leaderboard")?;
+
+        let owner_id = object
+            .get("owner_id")
+            .and_then(number)
+            .ok_or("missing `owner_id` of leaderboard")?;
+
+        let mut members = object
+            .get("members")
+            .and_then(|x| x.get::<HashMap<String, JsonValue>>())
+            .ok_or("missing `members` of leaderboard")?
+            .values()
+            .map(parse_member)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        members.sort_by_key(|member| member.id);
+
+        Ok(Self {
+            year,
+            owner_id,
+            members,
+        })
+    }
+
+    #[must_use]
+    pub fn member(&self, id: u64) -> Option<&Member> {
+        self.members.iter().find(|member| member.id == id)
+    }
+
+    /// The member to show progress for: the user `AOC_USER_ID`, or the owner of the leaderboard.
+    pub fn current_member(&self) -> Result<&Member, String> {
+        let id = user_id().unwrap_or(self.owner_id);
+        self.member(id)
+            .ok_or_else(|| format!("user {id} is not a member of the leaderboard."))
+    }
+}
+
+impl Member {
+    /// The name of the member, anonymous members are shown like on the website.
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+fn parse_member(value: &JsonValue) -> Result<Member, String> {
+    let object: &HashMap<String, JsonValue> =
+        value.get().ok_or("expected a member to be an object")?;
+
+    let id = object
+        .get("id")
+        .and_then(number)
+        .ok_or("missing `id` of member")?;
+
+    let field = |key: &str| {
+        object
+            .get(key)
+            .and_then(number)
+            .ok_or_else(|| format!("missing `{key}` of member {id}"))
+    };
+
+    let name = match object.get("name") {
+        Some(JsonValue::String(name)) => Some(name.clone()),
+        _ => None,
+    };
+
+    let mut days = BTreeMap::new();
+
+    if let Some(levels) = object
+        .get("completion_day_level")
+        .and_then(|x| x.get::<HashMap<String, JsonValue>>())
+    {
+        for (day, parts) in levels {
+            let day = day
+                .parse::<u8>()
+                .ok()
+                .and_then(Day::new)
+                .ok_or_else(|| format!("invalid day `{day}` of member {id}"))?;
+
+            let timestamp = |part: &str| {
+                parts
+                    .get::<HashMap<String, JsonValue>>()?
+                    .get(part)?
+                    .get::<HashMap<String, JsonValue>>()?
+                    .get("get_star_ts")
+                    .and_then(number)
+            };
+
+            days.insert(
+                day,
+                Completion {
+                    part_1: timestamp("1"),
+                    part_2: timestamp("2"),
+                },
+            );
+        }
+    }
+
+    Ok(Member {
+        id,
+        name,
+        stars: u32::try_from(field("stars")?)
+            .map_err(|_| format!("invalid `stars` of member {id}"))?,
+        local_score: field("local_score")?,
+        days,
+    })
+}
+
+fn number<T: TryFrom<u64>>(value: &JsonValue) -> Option<T> {
+    match value {
+        JsonValue::Number(x) => to_integer(*x),
+        _ => None,
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_integer<T: TryFrom<u64>>(x: f64) -> Option<T> {
+    (x >= 0.0 && x.fract() == 0.0)
+        .then(|| T::try_from(x as u64).ok())
+        .flatten()
+}
+
+/// The user id set by the `AOC_USER_ID` environment variable.
+#[must_use]
+pub fn user_id() -> Option<u64> {
+    env::var("AOC_USER_ID").ok()?.trim().parse().ok()
+}
+
+#[must_use]
+pub fn get_path(year: u16) -> String {
+    format!("data/{year}/leaderboard.json")
+}
+
+/// Loads the leaderboard of a year from the JSON file at `path`. Without a path, the private leaderboard of the user
+/// `AOC_USER_ID` is downloaded, unless it was downloaded less than 15 minutes ago.
+pub fn load(year: u16, path: Option<&str>) -> Result<Leaderboard, String> {
+    let json = match path {
+        Some(path) => {
+            fs::read_to_string(path).map_err(|e| format!("could not read \"{path}\": {e}"))?
+        }
+        None => match read_cache(year) {
+            Some(json) => json,
+            None => {
+                let id = user_id().ok_or(
+                    "no user id found. Set AOC_USER_ID to the id of your private leaderboard.",
+                )?;
+                let client = AocClient::from_env().map_err(|e| e.to_string())?;
+                let json = client
+                    .get_leaderboard(year, id)
+                    .map_err(|e| e.to_string())?;
+                aoc_client::write_file(&get_path(year), &json).map_err(|e| e.to_string())?;
+                json
+            }
+        },
+    };
+
+    let leaderboard = Leaderboard::parse(&json)?;
+
+    if leaderboard.year != year {
+        return Err(format!(
+            "the leaderboard belongs to {}, not {year}.",
+            leaderboard.year
+        ));
+    }
+
+    Ok(leaderboard)
+}
+
+/// Reads the cached leaderboard of a year, if it is recent enough.
+fn read_cache(year: u16) -> Option<String> {
+    let path = get_path(year);
+    let age = fs::metadata(&path).ok()?.modified().ok()?.elapsed().ok()?;
+    (age < CACHE_DURATION)
+        .then(|| fs::read_to_string(&path).ok())
+        .flatten()
+}
+
+/// The unix timestamp at which the puzzle of a day unlocks.
+#[must_use]
+pub fn unlock_time(year: u16, day: Day) -> u64 {
+    // days since the unix epoch of December `day`, see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = u64::from(year);
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (12 - 3) + 2) / 5 + u64::from(day.into_inner()) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    days * 24 * 60 * 60 + UNLOCK_OFFSET_SECS
+}
+
+/// How long a star took after its puzzle unlocked.
+#[must_use]
+pub fn time_to_star(year: u16, day: Day, timestamp: u64) -> Duration {
+    Duration::from_secs(timestamp.saturating_sub(unlock_time(year, day)))
+}
+
+/// Returns the current unix timestamp.
+#[must_use]
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{time_to_star, unlock_time, Completion, Leaderboard};
+    use crate::day;
+
+    const LEADERBOARD: &str = r#"{
+        "event": "2023",
+        "owner_id": 3031,
+        "members": {
+            "3031": {
+                "id": 3031, "name": "Ferris", "stars": 3, "local_score": 10, "global_score": 0,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1701406800, "star_index": 1 }, "2": { "get_star_ts": 1701407400, "star_index": 2 } },
+                    "3": { "1": { "get_star_ts": 1701580000, "star_index": 3 } }
+                }
+            },
+            "42": { "id": 42, "name": null, "stars": 0, "local_score": 0, "completion_day_level": {} }
+        }
+    }"#;
+
+    #[test]
+    fn parses_leaderboards() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        assert_eq!(leaderboard.year, 2023);
+        assert_eq!(leaderboard.owner_id, 3031);
+        assert_eq!(leaderboard.members.len(), 2);
+        assert_eq!(leaderboard.member(42).unwrap().name, None);
+
+        let owner = leaderboard.member(3031).unwrap();
+        assert_eq!(owner.stars, 3);
+        assert_eq!(
+            owner.days.get(&day!(3)),
+            Some(&Completion {
+                part_1: Some(1_701_580_000),
+                part_2: None,
+            })
+        );
+
+        assert!(Leaderboard::parse("{\"event\": \"2023\"}").is_err());
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        // 2023-12-01T05:00:00Z
+        assert_eq!(unlock_time(2023, day!(1)), 1_701_406_800);
+        // 2020-12-25T05:00:00Z
+        assert_eq!(unlock_time(2020, day!(25)), 1_608_872_400);
+        assert_eq!(
+            time_to_star(2023, day!(1), 1_701_407_400),
+            Duration::from_secs(600)
+        );
+    }
+}
@@ -0,0 +1,253 @@
+/// Time and memory caps for solution runs, so a runaway day cannot stall `cargo all`.
+/// Caps of a year live in `data/{year}/limits.txt`. Lines without a day apply to every day, lines prefixed with a day override them:
+///
+/// ```text
+/// timeout: 10s
+/// memory: 1GB
+/// 14 timeout: 1m
+/// ```
+use std::{collections::HashMap, fmt::Display, fs, io, time::Duration};
+
+use crate::Day;
+
+/// The caps of a run. Caps that are [`None`] are not enforced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximum time of a single execution of each part, benchmark iterations are not counted.
+    pub timeout: Option<Duration>,
+    /// Maximum resident memory in bytes.
+    pub memory: Option<u64>,
+}
+
+impl Limits {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.timeout.is_none() && self.memory.is_none()
+    }
+
+    /// Combines two sets of caps, caps set in `other` take precedence.
+    #[must_use]
+    pub fn or(self, other: Limits) -> Self {
+        Self {
+            timeout: other.timeout.or(self.timeout),
+            memory: other.memory.or(self.memory),
+        }
+    }
+}
+
+/// The caps of all days of a year.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LimitsConfig {
+    pub global: Limits,
+    pub days: HashMap<Day, Limits>,
+}
+
+impl LimitsConfig {
+    /// The caps of a day: its own caps, falling back to the global ones.
+    #[must_use]
+    pub fn for_day(&self, day: Day) -> Limits {
+        self.days
+            .get(&day)
+            .map_or(self.global, |limits| self.global.or(*limits))
+    }
+
+    fn parse(s: &str) -> Result<Self, LimitsError> {
+        let mut config = Self::default();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |message: String| LimitsError::Parse {
+                line: i + 1,
+                message,
+            };
+
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| error("expected `<cap>: <value>`".into()))?;
+
+            let (limits, cap) = match key.trim().split_once(' ') {
+                Some((day, cap)) => {
+                    let day = day
+                        .parse::<Day>()
+                        .map_err(|_| error(format!("invalid day `{day}`")))?;
+                    (config.days.entry(day).or_default(), cap.trim())
+                }
+                None => (&mut config.global, key.trim()),
+            };
+
+            match cap {
+                "timeout" => limits.timeout = Some(parse_timeout(value.trim()).map_err(error)?),
+                "memory" => limits.memory = Some(parse_memory(value.trim()).map_err(error)?),
+                _ => return Err(error(format!("unknown cap `{cap}`"))),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+#[must_use]
+pub fn get_path(year: u16) -> String {
+    format!("data/{year}/limits.txt")
+}
+
+/// Reads the caps of a year. A missing file means that no caps apply.
+pub fn read(year: u16) -> Result<LimitsConfig, LimitsError> {
+    match fs::read_to_string(get_path(year)) {
+        Ok(s) => LimitsConfig::parse(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(LimitsConfig::default()),
+        Err(e) => Err(LimitsError::IO(e)),
+    }
+}
+
+/// Parses a timeout like `500ms`, `10s` or `2m`. Plain numbers are seconds.
+pub fn parse_timeout(s: &str) -> Result<Duration, String> {
+    let (number, unit_secs) = if let Some(x) = s.strip_suffix("ms") {
+        (x, 0.001)
+    } else if let Some(x) = s.strip_suffix('s') {
+        (x, 1.0)
+    } else if let Some(x) = s.strip_suffix('m') {
+        (x, 60.0)
+    } else {
+        (s, 1.0)
+    };
+
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|x| *x > 0.0)
+        .and_then(|x| Duration::try_from_secs_f64(x * unit_secs).ok())
+        .ok_or_else(|| format!("invalid timeout `{s}`, expecting e.g. `500ms`, `10s` or `2m`"))
+}
+
+/// Parses a memory size like `512KB`, `256MB` or `2GB` into bytes. Plain numbers are megabytes.
+pub fn parse_memory(s: &str) -> Result<u64, String> {
+    let upper = s.to_ascii_uppercase();
+
+    let (number, unit) = if let Some(x) = upper.strip_suffix("KB") {
+        (x, 1 << 10)
+    } else if let Some(x) = upper.strip_suffix("MB") {
+        (x, 1 << 20)
+    } else if let Some(x) = upper.strip_suffix("GB") {
+        (x, 1 << 30)
+    } else {
+        (upper.as_str(), 1 << 20)
+    };
+
+    number
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|x| *x > 0)
+        .and_then(|x| x.checked_mul(unit))
+        .ok_or_else(|| format!("invalid memory size `{s}`, expecting e.g. `512MB` or `2GB`"))
+}
+
+/// Formats a memory size in bytes for display, e.g. `512MB`.
+#[must_use]
+pub fn format_memory(bytes: u64) -> String {
+    if bytes >= 1 << 30 && bytes.is_multiple_of(1 << 30) {
+        format!("{}GB", bytes >> 30)
+    } else if bytes >= 1 << 20 {
+        format!("{}MB", bytes >> 20)
+    } else {
+        format!("{}KB", bytes >> 10)
+    }
+}
+
+/// Whether memory caps can be enforced on this platform. The memory of a run is read from `/proc`.
+#[must_use]
+pub fn supports_memory_limit() -> bool {
+    cfg!(target_os = "linux")
+}
+
+/// Returns the resident memory of a process in bytes, or [`None`] if it cannot be determined.
+#[must_use]
+pub fn resident_memory(pid: u32) -> Option<u64> {
+    let status = fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kilobytes = line
+        .trim_start_matches("VmRSS:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// Describes why a run was stopped, e.g. `exceeded the time limit of 10s`.
+#[must_use]
+pub fn describe(limits: &Limits, timed_out: bool) -> String {
+    match (timed_out, limits.timeout, limits.memory) {
+        (true, Some(timeout), _) => format!("exceeded the time limit of {timeout:?}"),
+        (false, _, Some(memory)) => {
+            format!("exceeded the memory limit of {}", format_memory(memory))
+        }
+        _ => "was stopped".into(),
+    }
+}
+
+#[derive(Debug)]
+pub enum LimitsError {
+    IO(io::Error),
+    Parse { line: usize, message: String },
+}
+
+impl std::error::Error for LimitsError {}
+
+impl Display for LimitsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitsError::IO(e) => write!(f, "{e}"),
+            LimitsError::Parse { line, message } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_memory, parse_timeout, Limits, LimitsConfig};
+    use crate::day;
+
+    #[test]
+    fn parses_caps_per_day() {
+        let config =
+            LimitsConfig::parse("# caps\ntimeout: 10s\nmemory: 1GB\n14 timeout: 1m\n").unwrap();
+
+        assert_eq!(
+            config.for_day(day!(1)),
+            Limits {
+                timeout: Some(Duration::from_secs(10)),
+                memory: Some(1 << 30),
+            }
+        );
+        assert_eq!(
+            config.for_day(day!(14)),
+            Limits {
+                timeout: Some(Duration::from_secs(60)),
+                memory: Some(1 << 30),
+            }
+        );
+
+        assert!(LimitsConfig::parse("14 speed: 1s").is_err());
+        assert!(LimitsConfig::parse("timeout 1s").is_err());
+    }
+
+    #[test]
+    fn parses_units() {
+        assert_eq!(parse_timeout("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_timeout("2"), Ok(Duration::from_secs(2)));
+        assert!(parse_timeout("-1s").is_err());
+        assert_eq!(parse_memory("512MB"), Ok(512 << 20));
+        assert_eq!(parse_memory("64kb"), Ok(64 << 10));
+        assert!(parse_memory("lots").is_err());
+    }
+}
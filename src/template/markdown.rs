@@ -0,0 +1,155 @@
+/// Minimal HTML to markdown conversion for puzzle descriptions.
+/// Only covers the small set of elements used on the Advent of Code website; unknown tags are dropped and their text kept.
+use std::fmt::Write;
+
+#[must_use]
+pub fn from_html(html: &str) -> String {
+    let mut out = String::new();
+    let mut links: Vec<String> = vec![];
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(tag) = rest.strip_prefix('<') {
+            let end = tag.find('>').unwrap_or(tag.len());
+            let (tag, remainder) = tag.split_at(end);
+            rest = remainder.get(1..).unwrap_or("");
+
+            let closing = tag.starts_with('/');
+            let name = tag
+                .trim_start_matches('/')
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or("")
+                .to_ascii_lowercase();
+
+            match (name.as_str(), closing) {
+                ("h2", false) => {
+                    end_block(&mut out);
+                    out.push_str("## ");
+                }
+                ("p" | "h2" | "ul", true) => end_block(&mut out),
+                ("pre", false) => {
+                    end_block(&mut out);
+                    out.push_str("```\n");
+                    in_pre = true;
+                }
+                ("pre", true) => {
+                    if !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```");
+                    end_block(&mut out);
+                    in_pre = false;
+                }
+                ("code", _) if !in_pre => out.push('`'),
+                ("em", _) if !in_pre => out.push('*'),
+                ("li", false) => out.push_str("- "),
+                ("li", true) => out.push('\n'),
+                ("a", false) => {
+                    links.push(get_attribute(tag, "href").unwrap_or_default());
+                    out.push('[');
+                }
+                ("a", true) => {
+                    if let Some(href) = links.pop() {
+                        let _ = write!(out, "]({href})");
+                    }
+                }
+                _ => {}
+            }
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = &rest[..end];
+            rest = &rest[end..];
+
+            // whitespace between block elements is layout of the HTML source, not content.
+            if in_pre || !text.trim().is_empty() || !text.contains('\n') {
+                out.push_str(&decode_entities(text));
+            }
+        }
+    }
+
+    let mut markdown = out.trim().to_string();
+    markdown.push('\n');
+    markdown
+}
+
+fn end_block(out: &mut String) {
+    let trimmed = out.trim_end_matches([' ', '\n']).len();
+    out.truncate(trimmed);
+    if !out.is_empty() {
+        out.push_str("\n\n");
+    }
+}
+
+fn get_attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let len = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + len]))
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let c = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                entity => {
+                    let code = entity.strip_prefix('#')?;
+                    let code = match code.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => code.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode_entities, from_html};
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("a &lt; b &amp;&amp; c&#39;s &#x41; & d"),
+            "a < b && c's A & d"
+        );
+    }
+
+    #[test]
+    fn converts_puzzle_html() {
+        let html = "<h2>--- Day 1: Test ---</h2>\n<p>Find the <em>sum</em> of <code>1 &lt; 2</code>, see <a href=\"/2023/about\">here</a>.</p>\n<pre><code>1abc2\npqr3stu8vwx\n</code></pre>\n<ul>\n<li>one</li>\n<li>two</li>\n</ul>\n<p>Answer: <code><em>142</em></code>.</p>";
+        assert_eq!(
+            from_html(html),
+            "## --- Day 1: Test ---\n\nFind the *sum* of `1 < 2`, see [here](/2023/about).\n\n```\n1abc2\npqr3stu8vwx\n```\n\n- one\n- two\n\nAnswer: `*142*`.\n"
+        );
+    }
+}
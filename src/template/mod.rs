@@ -1,36 +1,154 @@
-use crate::Day;
-use std::{env, fs};
+use crate::PuzzleId;
+use std::{env, fs, io};
 
-pub mod aoc_cli;
+pub mod alloc;
+pub mod answers;
+pub mod aoc_client;
+pub mod bench_history;
 pub mod commands;
+pub mod error;
+pub mod examples;
+pub mod input;
+pub mod isolated;
+pub mod leaderboard;
+pub mod limits;
+pub mod markdown;
 pub mod readme_benchmarks;
+pub mod readme_stars;
+pub mod record;
+pub mod registry;
+pub mod reports;
 pub mod runner;
+pub mod stats;
+pub mod submissions;
+pub mod visualize;
+pub mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+
+/// Returns the path of the data file of a puzzle, e.g. `data/2023/inputs/01.txt`.
+#[must_use]
+pub fn get_data_path(folder: &str, puzzle: PuzzleId, extension: &str) -> String {
+    format!("data/{}/{folder}/{}.{extension}", puzzle.year, puzzle.day)
+}
+
+/// Returns the path of the n-th data file of a puzzle in a folder, starting from 1.
+/// The first file is the one returned by [`get_data_path`], further files are numbered, e.g. `data/2023/examples/01-2.txt`.
+#[must_use]
+pub fn get_data_path_part(folder: &str, puzzle: PuzzleId, index: usize, extension: &str) -> String {
+    if index <= 1 {
+        return get_data_path(folder, puzzle, extension);
+    }
+    format!(
+        "data/{}/{folder}/{}-{index}.{extension}",
+        puzzle.year, puzzle.day
+    )
+}
+
+/// Whether a data file still has to be filled in, e.g. an input that scaffold created empty.
+pub(crate) fn is_missing_or_empty(path: &str) -> Result<bool, io::Error> {
+    match fs::metadata(path) {
+        Ok(metadata) => Ok(metadata.len() == 0),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(true),
+        Err(e) => Err(e),
+    }
+}
+
+/// Removes terminal color codes, which depend on the terminal the tests run in.
+#[cfg(feature = "test_lib")]
+pub(crate) fn strip_ansi(s: &str) -> String {
+    regex::Regex::new(r"\x1b\[[0-9;]*m")
+        .unwrap()
+        .replace_all(s, "")
+        .into_owned()
+}
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
+    read_file_part(folder, puzzle, 1)
+}
+
+/// Helper function that reads the n-th text file of a folder to a string, e.g. the second example of a day.
+#[must_use]
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, index: usize) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(get_data_path_part(folder, puzzle, index, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constant `PUZZLE` and sets up the input and runner for each part.
+/// Also exports the constant `SOLUTION` that registers both parts for in-process runs.
+///
+/// Solutions that share parsing between both parts can pass a `parse(&str) -> Result<Input, E>` function as third argument,
+/// e.g. `solution!(2023, 4, parse)`. The input is then parsed once, and both parts take `&Input` instead of `&str`
+/// (or what it dereferences to, e.g. `&[T]` for a `Vec<T>`). The parse phase is timed separately from the parts.
+/// If parsing fails, its error is reported like the error of a part and both parts are skipped.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        /// The current day.
-        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+    ($year:expr, $day:expr) => {
+        /// The current puzzle.
+        const PUZZLE: advent_of_code::PuzzleId = advent_of_code::puzzle!($year, $day);
+
+        /// Registry entry for the current day.
+        #[allow(dead_code)]
+        pub const SOLUTION: advent_of_code::template::registry::Solution =
+            advent_of_code::template::registry::Solution::new(
+                PUZZLE,
+                false,
+                |input, bench, progress, on_record| {
+                    use advent_of_code::template::runner::run;
+                    on_record(run(part_one, input, PUZZLE, 1, bench, progress));
+                    on_record(run(part_two, input, PUZZLE, 2, bench, progress));
+                },
+            );
+
+        #[allow(dead_code)]
+        fn main() {
+            use advent_of_code::template::runner::*;
+            let input = advent_of_code::template::input::read_input(PUZZLE);
+            run_part(part_one, &input, PUZZLE, 1);
+            run_part(part_two, &input, PUZZLE, 2);
+        }
+    };
+    ($year:expr, $day:expr, $parse:expr) => {
+        /// The current puzzle.
+        const PUZZLE: advent_of_code::PuzzleId = advent_of_code::puzzle!($year, $day);
+
+        /// Registry entry for the current day.
+        #[allow(dead_code)]
+        pub const SOLUTION: advent_of_code::template::registry::Solution =
+            advent_of_code::template::registry::Solution::new(
+                PUZZLE,
+                true,
+                |input, bench, progress, on_record| {
+                    use advent_of_code::template::runner::{run, run_parse, skipped_parts};
+                    let (parsed, record) = run_parse($parse, input, PUZZLE, bench, progress);
+                    on_record(record);
+                    let Some(parsed) = parsed else {
+                        skipped_parts(PUZZLE).into_iter().for_each(on_record);
+                        return;
+                    };
+                    on_record(run(|x| part_one(x), &parsed, PUZZLE, 1, bench, progress));
+                    on_record(run(|x| part_two(x), &parsed, PUZZLE, 2, bench, progress));
+                },
+            );
 
+        #[allow(dead_code)]
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let input = advent_of_code::template::input::read_input(PUZZLE);
+            // a day may define a `parse_input` function of its own, which takes precedence over the glob import.
+            let parsed = advent_of_code::template::runner::parse_input($parse, &input, PUZZLE);
+            if let Some(parsed) = parsed {
+                run_part(|x| part_one(x), &parsed, PUZZLE, 1);
+                run_part(|x| part_two(x), &parsed, PUZZLE, 2);
+            }
         }
     };
 }
@@ -1,8 +1,10 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{io, time::Duration};
 
-use crate::Day;
+use crate::template::alloc::{format_bytes, MemoryStats};
+use crate::template::reports::Formatter;
+use crate::PuzzleId;
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -20,24 +22,29 @@ impl From<std::io::Error> for Error {
 
 #[derive(Clone)]
 pub struct Timings {
-    pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub puzzle: PuzzleId,
+    /// Duration of the parse phase, present for solutions that parse their input up front.
+    pub parse: Option<Duration>,
+    pub part_1: Option<Duration>,
+    pub part_2: Option<Duration>,
+    /// Heap usage of both parts, present if the `alloc_stats` feature is enabled.
+    pub part_1_memory: Option<MemoryStats>,
+    pub part_2_memory: Option<MemoryStats>,
     pub total_nanos: f64,
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub(crate) pos_start: usize,
+    pub(crate) pos_end: usize,
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{}.rs", puzzle.bin_name())
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -58,26 +65,66 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
+/// Formats one memory statistic of both parts as a table cell, e.g. `1.5KB / -` if part two was not measured.
+pub(crate) fn memory_cell(timing: &Timings, stat: impl Fn(&MemoryStats) -> String) -> String {
+    [timing.part_1_memory, timing.part_2_memory]
+        .iter()
+        .map(|memory| memory.as_ref().map_or("-".into(), &stat))
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+/// Builds the benchmark table. Columns for the parse phase and heap usage are only added if any day has them.
+fn construct_table(prefix: &str, timings: &[Timings], total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
+    let has_parse = timings.iter().any(|x| x.parse.is_some());
+    let has_memory = timings
+        .iter()
+        .any(|x| x.part_1_memory.is_some() || x.part_2_memory.is_some());
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let (parse_header, parse_align) = if has_parse {
+        (" Parse |", " :---: |")
+    } else {
+        ("", "")
+    };
+    let (memory_header, memory_align) = if has_memory {
+        (" Peak memory | Allocations |", " :---: | :---: |")
+    } else {
+        ("", "")
+    };
+
+    lines.push(format!(
+        "| Day |{parse_header} Part 1 | Part 2 |{memory_header}"
+    ));
+    lines.push(format!(
+        "| :---: |{parse_align} :---: | :---:  |{memory_align}"
+    ));
 
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+        let path = get_path_for_bin(timing.puzzle);
+        let mut line = format!("| [Day {}]({}) |", timing.puzzle.day.into_inner(), path);
+
+        if has_parse {
+            line.push_str(&format!(" `{}` |", format_timing(timing.parse)));
+        }
+
+        line.push_str(&format!(
+            " `{}` | `{}` |",
+            format_timing(timing.part_1),
+            format_timing(timing.part_2)
         ));
+
+        if has_memory {
+            line.push_str(&format!(
+                " {} | {} |",
+                memory_cell(timing, |x| format!("`{}`", format_bytes(x.peak_bytes))),
+                memory_cell(timing, |x| format!("`{}`", x.allocations))
+            ));
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
@@ -87,44 +134,70 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
+/// Formats the duration of a part for display, e.g. `1.5ms`.
+#[must_use]
+pub fn format_timing(duration: Option<Duration>) -> String {
+    duration.map_or_else(|| "-".into(), |x| format!("{x:.1?}"))
+}
+
+fn update_content(s: &mut String, timings: &[Timings], total_millis: f64) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
     let table = construct_table("##", timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
-    Ok(())
+/// Rewrites the benchmark table between the markers of an existing markdown file, usually `README.md`.
+pub struct Readme;
+
+impl Formatter for Readme {
+    fn render(
+        &self,
+        timings: &[Timings],
+        total_millis: f64,
+        existing: Option<&str>,
+    ) -> Result<String, Error> {
+        let mut readme = existing.unwrap_or_default().to_string();
+        update_content(&mut readme, timings, total_millis)?;
+        Ok(readme)
+    }
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use std::time::Duration;
+
+    use super::{construct_table, update_content, Timings, MARKER};
+    use crate::puzzle;
+    use crate::template::alloc::MemoryStats;
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
-                day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
+                puzzle: puzzle!(2023, 1),
+                parse: None,
+                part_1: Some(Duration::from_millis(10)),
+                part_2: Some(Duration::from_millis(20)),
+                part_1_memory: None,
+                part_2_memory: None,
                 total_nanos: 3e+10,
             },
             Timings {
-                day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
+                puzzle: puzzle!(2023, 2),
+                parse: None,
+                part_1: Some(Duration::from_millis(30)),
+                part_2: Some(Duration::from_millis(40)),
+                part_1_memory: None,
+                part_2_memory: None,
                 total_nanos: 7e+10,
             },
             Timings {
-                day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
+                puzzle: puzzle!(2023, 4),
+                parse: None,
+                part_1: Some(Duration::from_millis(40)),
+                part_2: Some(Duration::from_millis(50)),
+                part_1_memory: None,
+                part_2_memory: None,
                 total_nanos: 9e+10,
             },
         ]
@@ -134,28 +207,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,7 +236,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -172,9 +245,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2023_01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2023_02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2023_04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -183,4 +256,34 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn adds_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings[0].part_1_memory = Some(MemoryStats {
+            peak_bytes: 1536,
+            allocations: 12,
+        });
+
+        let table = construct_table("##", &timings, 190.0);
+        assert!(table.contains("| Day | Part 1 | Part 2 | Peak memory | Allocations |"));
+        assert!(table.contains(
+            "| [Day 1](./src/bin/2023_01.rs) | `10.0ms` | `20.0ms` | `1.5KB` / - | `12` / - |"
+        ));
+        assert!(table
+            .contains("| [Day 2](./src/bin/2023_02.rs) | `30.0ms` | `40.0ms` | - / - | - / - |"));
+    }
+
+    #[test]
+    fn adds_parse_column() {
+        let mut timings = get_mock_timings();
+        timings[2].parse = Some(Duration::from_millis(5));
+
+        let table = construct_table("##", &timings, 190.0);
+        assert!(
+            table.contains("| Day | Parse | Part 1 | Part 2 |\n| :---: | :---: | :---: | :---:  |")
+        );
+        assert!(table.contains("| [Day 2](./src/bin/2023_02.rs) | `-` | `30.0ms` | `40.0ms` |"));
+        assert!(table.contains("| [Day 4](./src/bin/2023_04.rs) | `5.0ms` | `40.0ms` | `50.0ms` |"));
+    }
 }
@@ -0,0 +1,119 @@
+/// Module that updates the readme with the ⭐️ progress of a private leaderboard member.
+/// The table has the same layout as the one of the `advent-readme-stars` action, which used to fill in the marker.
+use std::fs;
+
+use crate::template::aoc_client::DEFAULT_BASE_URL;
+use crate::template::leaderboard::Member;
+use crate::template::readme_benchmarks::{locate_table, Error};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+fn star(solved: bool) -> &'static str {
+    if solved {
+        "⭐"
+    } else {
+        " "
+    }
+}
+
+fn construct_table(year: u16, member: &Member) -> String {
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for (day, completion) in &member.days {
+        lines.push(format!(
+            "| [Day {}]({DEFAULT_BASE_URL}/{year}/day/{}) | {} | {} |",
+            day.into_inner(),
+            day.into_inner(),
+            star(completion.part_1.is_some()),
+            star(completion.part_2.is_some()),
+        ));
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, year: u16, member: &Member) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table(year, member);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(year: u16, member: &Member) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, member)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{update_content, MARKER};
+    use crate::day;
+    use crate::template::leaderboard::{Completion, Member};
+
+    fn get_mock_member() -> Member {
+        Member {
+            id: 3031,
+            name: Some("Ferris".into()),
+            stars: 3,
+            local_score: 10,
+            days: BTreeMap::from([
+                (
+                    day!(1),
+                    Completion {
+                        part_1: Some(1),
+                        part_2: Some(2),
+                    },
+                ),
+                (
+                    day!(3),
+                    Completion {
+                        part_1: Some(3),
+                        part_2: None,
+                    },
+                ),
+            ]),
+        }
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{MARKER}\n{MARKER}\nbaz");
+        update_content(&mut s, 2023, &get_mock_member()).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 3](https://adventofcode.com/2023/day/3) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+
+        update_content(&mut s, 2023, &get_mock_member()).unwrap();
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, 2023, &get_mock_member()).unwrap();
+    }
+}
@@ -0,0 +1,356 @@
+/// Machine-readable results of solution runs.
+/// Solution binaries emit one record per part when called with `--format json`, which lets commands like `all` consume results without parsing the human-readable output.
+use std::{collections::HashMap, fmt::Display, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::alloc::MemoryStats;
+use crate::template::stats::Stats;
+use crate::{Day, PuzzleId};
+
+/// Output format of a solution run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = ParseFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(ParseFormatError),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Text => f.write_str("text"),
+            OutputFormat::Json => f.write_str("json"),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct ParseFormatError;
+
+impl std::error::Error for ParseFormatError {}
+
+impl Display for ParseFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of the formats `text` or `json`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Outcome of a solution part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    Unsolved,
+    /// The part returned an error.
+    Error,
+    /// The run was stopped after exceeding its time limit.
+    Timeout,
+    /// The run was stopped after exceeding its memory limit.
+    OutOfMemory,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Error => "error",
+            Status::Timeout => "timeout",
+            Status::OutOfMemory => "out_of_memory",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "solved" => Some(Status::Solved),
+            "unsolved" => Some(Status::Unsolved),
+            "error" => Some(Status::Error),
+            "timeout" => Some(Status::Timeout),
+            "out_of_memory" => Some(Status::OutOfMemory),
+            _ => None,
+        }
+    }
+}
+
+/// The part number of the parse phase, which runs once before both parts of solutions that parse their input up front.
+pub const PARSE: u8 = 0;
+
+/// Names a part for display, e.g. `Part 1`, or `Parse` for the parse phase.
+#[must_use]
+pub fn part_label(part: u8) -> String {
+    if part == PARSE {
+        "Parse".into()
+    } else {
+        format!("Part {part}")
+    }
+}
+
+/// The result of running one part of a solution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub puzzle: PuzzleId,
+    /// The part number, [`PARSE`] for the parse phase.
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration: Duration,
+    pub samples: u128,
+    pub status: Status,
+    /// Benchmark statistics, present if the part was run with `--time`.
+    pub stats: Option<Stats>,
+    /// Whether the answer matches the confirmed answer, [`None`] if no answer was confirmed yet.
+    pub verified: Option<bool>,
+    /// The error chain returned by the part, or the reason the run was stopped. Present if the part did not finish.
+    pub error: Option<String>,
+    /// Heap usage of the part, present if the `alloc_stats` feature is enabled.
+    pub memory: Option<MemoryStats>,
+}
+
+impl Record {
+    /// Serializes the record into a single line of JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut object: HashMap<String, JsonValue> = HashMap::new();
+        object.insert("year".into(), f64::from(self.puzzle.year).into());
+        object.insert("day".into(), f64::from(self.puzzle.day.into_inner()).into());
+        object.insert("part".into(), f64::from(self.part).into());
+        object.insert(
+            "answer".into(),
+            self.answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        object.insert(
+            "duration_nanos".into(),
+            (self.duration.as_nanos() as f64).into(),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        object.insert("samples".into(), (self.samples as f64).into());
+        object.insert("status".into(), self.status.as_str().to_string().into());
+        object.insert(
+            "verified".into(),
+            self.verified.map_or(JsonValue::Null, JsonValue::Boolean),
+        );
+        object.insert(
+            "error".into(),
+            self.error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        object.insert(
+            "stats".into(),
+            self.stats.as_ref().map_or(JsonValue::Null, stats_to_json),
+        );
+        object.insert(
+            "memory".into(),
+            self.memory.as_ref().map_or(JsonValue::Null, memory_to_json),
+        );
+
+        // stringifying only fails for non-finite numbers, which a record never contains.
+        JsonValue::from(object).stringify().unwrap()
+    }
+
+    /// Parses a record from a line of JSON. Returns [`None`] if the line is not a valid record.
+    #[must_use]
+    pub fn from_json(line: &str) -> Option<Self> {
+        let value: JsonValue = line.trim().parse().ok()?;
+        let object: &HashMap<String, JsonValue> = value.get()?;
+
+        let number = |key: &str| -> Option<f64> { object.get(key)?.get::<f64>().copied() };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let puzzle = PuzzleId::new(number("year")? as u16, Day::new(number("day")? as u8)?)?;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = number("part")? as u8;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let duration = Duration::from_nanos(number("duration_nanos")? as u64);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = number("samples")? as u128;
+        let status = Status::parse(object.get("status")?.get::<String>()?)?;
+
+        let answer = match object.get("answer")? {
+            JsonValue::String(s) => Some(s.clone()),
+            JsonValue::Null => None,
+            _ => return None,
+        };
+
+        let verified = match object.get("verified") {
+            Some(JsonValue::Boolean(verified)) => Some(*verified),
+            Some(JsonValue::Null) | None => None,
+            _ => return None,
+        };
+
+        let error = match object.get("error") {
+            Some(JsonValue::String(error)) => Some(error.clone()),
+            Some(JsonValue::Null) | None => None,
+            _ => return None,
+        };
+
+        let stats = match object.get("stats") {
+            Some(JsonValue::Object(stats)) => Some(stats_from_json(stats)?),
+            Some(JsonValue::Null) | None => None,
+            _ => return None,
+        };
+
+        let memory = match object.get("memory") {
+            Some(JsonValue::Object(memory)) => Some(memory_from_json(memory)?),
+            Some(JsonValue::Null) | None => None,
+            _ => return None,
+        };
+
+        Some(Record {
+            puzzle,
+            part,
+            answer,
+            duration,
+            samples,
+            status,
+            stats,
+            verified,
+            error,
+            memory,
+        })
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn stats_to_json(stats: &Stats) -> JsonValue {
+    let mut object: HashMap<String, JsonValue> = HashMap::new();
+    for (key, duration) in [
+        ("mean_nanos", stats.mean),
+        ("min_nanos", stats.min),
+        ("median_nanos", stats.median),
+        ("p95_nanos", stats.p95),
+        ("std_dev_nanos", stats.std_dev),
+    ] {
+        object.insert(key.into(), (duration.as_nanos() as f64).into());
+    }
+    object.insert("outliers".into(), (stats.outliers as f64).into());
+    object.into()
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn stats_from_json(object: &HashMap<String, JsonValue>) -> Option<Stats> {
+    let number = |key: &str| -> Option<f64> { object.get(key)?.get::<f64>().copied() };
+    let duration = |key: &str| number(key).map(|x| Duration::from_nanos(x as u64));
+
+    Some(Stats {
+        mean: duration("mean_nanos")?,
+        min: duration("min_nanos")?,
+        median: duration("median_nanos")?,
+        p95: duration("p95_nanos")?,
+        std_dev: duration("std_dev_nanos")?,
+        outliers: number("outliers")? as u128,
+    })
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn memory_to_json(memory: &MemoryStats) -> JsonValue {
+    let mut object: HashMap<String, JsonValue> = HashMap::new();
+    object.insert("peak_bytes".into(), (memory.peak_bytes as f64).into());
+    object.insert("allocations".into(), (memory.allocations as f64).into());
+    object.into()
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn memory_from_json(object: &HashMap<String, JsonValue>) -> Option<MemoryStats> {
+    let number = |key: &str| -> Option<f64> { object.get(key)?.get::<f64>().copied() };
+
+    Some(MemoryStats {
+        peak_bytes: number("peak_bytes")? as usize,
+        allocations: number("allocations")? as usize,
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Record, Status};
+    use crate::puzzle;
+    use crate::template::alloc::MemoryStats;
+    use crate::template::stats::Stats;
+
+    #[test]
+    fn roundtrips_records() {
+        let record = Record {
+            puzzle: puzzle!(2023, 5),
+            part: 2,
+            answer: Some("multi\n\"line\"".into()),
+            duration: Duration::from_nanos(74_130),
+            samples: 100,
+            status: Status::Solved,
+            stats: Some(Stats {
+                mean: Duration::from_nanos(80_000),
+                min: Duration::from_nanos(70_000),
+                median: Duration::from_nanos(74_130),
+                p95: Duration::from_nanos(90_000),
+                std_dev: Duration::from_nanos(1_234),
+                outliers: 3,
+            }),
+            verified: Some(false),
+            error: None,
+            memory: Some(MemoryStats {
+                peak_bytes: 4096,
+                allocations: 12,
+            }),
+        };
+        assert_eq!(Record::from_json(&record.to_json()), Some(record));
+    }
+
+    #[test]
+    fn roundtrips_unsolved_records() {
+        let record = Record {
+            puzzle: puzzle!(2015, 25),
+            part: 1,
+            answer: None,
+            duration: Duration::from_nanos(12),
+            samples: 1,
+            status: Status::Unsolved,
+            stats: None,
+            verified: None,
+            error: None,
+            memory: None,
+        };
+        assert_eq!(Record::from_json(&record.to_json()), Some(record));
+    }
+
+    #[test]
+    fn roundtrips_failed_records() {
+        let record = Record {
+            puzzle: puzzle!(2023, 15),
+            part: 2,
+            answer: None,
+            duration: Duration::from_nanos(800),
+            samples: 1,
+            status: Status::Error,
+            stats: None,
+            verified: None,
+            error: Some("line 1, column 4: unknown operation\n  | rn?1\n  |    ^".into()),
+            memory: None,
+        };
+        assert_eq!(Record::from_json(&record.to_json()), Some(record));
+    }
+
+    #[test]
+    fn ignores_non_records() {
+        assert_eq!(Record::from_json("Part 1: 42 (1.2ms @ 100 samples)"), None);
+        assert_eq!(Record::from_json("{\"day\": 1}"), None);
+        assert_eq!(Record::from_json("[1, 2, 3]"), None);
+    }
+}
@@ -0,0 +1,99 @@
+/// Registry of solutions that can be run in-process.
+/// Every solution registers itself through the `solution!` macro, which exports a [`Solution`] constant named `SOLUTION`.
+/// The main binary collects these constants at build time (see `build.rs`), so commands like `all` can call solutions without spawning cargo.
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+
+use crate::template::isolated::fill_missing;
+use crate::template::record::{Record, Status, PARSE};
+use crate::template::runner::BenchOptions;
+use crate::PuzzleId;
+
+/// Runs a solution against an input, benchmarking it if options are passed. The flag controls whether progress is printed.
+/// The record of each part is passed to the callback as soon as the part finished, after the record of the parse phase
+/// for solutions that parse their input up front.
+pub type RunFn = fn(&str, Option<&BenchOptions>, bool, &mut dyn FnMut(Record));
+
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    /// Whether the solution has a parse phase, see [`solution!`](crate::solution).
+    pub has_parse: bool,
+    pub run: RunFn,
+}
+
+impl Solution {
+    #[must_use]
+    pub const fn new(puzzle: PuzzleId, has_parse: bool, run: RunFn) -> Self {
+        Self {
+            puzzle,
+            has_parse,
+            run,
+        }
+    }
+
+    /// Returns the part numbers of the records that a run reports, in order.
+    #[must_use]
+    pub fn parts(&self) -> &'static [u8] {
+        if self.has_parse {
+            &[PARSE, 1, 2]
+        } else {
+            &[1, 2]
+        }
+    }
+
+    /// Runs the solution like [`Solution::run`], but survives a panic: the part that panicked is reported with an
+    /// [`Status::Error`] record carrying the panic message, and the parts after it as not run.
+    pub fn run_catching(
+        &self,
+        input: &str,
+        time: Option<&BenchOptions>,
+        progress: bool,
+        emit: &mut dyn FnMut(Record),
+    ) {
+        let started = Instant::now();
+        let mut finished = vec![];
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            (self.run)(input, time, progress, &mut |record| {
+                finished.push(record.clone());
+                emit(record);
+            });
+        }));
+
+        if let Err(payload) = result {
+            let reason = format!("panicked: {}", panic_message(payload.as_ref()));
+            let reported: Vec<u8> = finished.iter().map(|record| record.part).collect();
+
+            fill_missing(
+                self.puzzle,
+                self.parts(),
+                finished,
+                Status::Error,
+                &reason,
+                started.elapsed(),
+            )
+            .into_iter()
+            .filter(|record| !reported.contains(&record.part))
+            .for_each(emit);
+        }
+    }
+}
+
+/// Extracts the message of a panic, which is a `&str` or a `String` for panics raised with `panic!`.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
+
+/// Finds the solution for a puzzle in a list of registered solutions.
+#[must_use]
+pub fn find(solutions: &[Solution], puzzle: PuzzleId) -> Option<&Solution> {
+    solutions.iter().find(|solution| solution.puzzle == puzzle)
+}
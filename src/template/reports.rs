@@ -0,0 +1,381 @@
+/// Benchmark reports written by `cargo time`, e.g. `--report csv:target/bench.csv`.
+/// Every format has its own [`Formatter`], the benchmark table in the readme is one of them.
+use std::{collections::HashMap, fmt::Display, fs, io, path::Path, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::alloc::{format_bytes, MemoryStats};
+use crate::template::readme_benchmarks::{format_timing, memory_cell, Error, Readme, Timings};
+use crate::template::record::PARSE;
+
+/// Renders benchmark timings into the contents of a report file.
+pub trait Formatter {
+    /// Renders the report. `existing` holds the current contents of the file, for formats that update a file in place.
+    fn render(
+        &self,
+        timings: &[Timings],
+        total_millis: f64,
+        existing: Option<&str>,
+    ) -> Result<String, Error>;
+}
+
+/// Format of a benchmark report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Readme,
+    Csv,
+    Json,
+    Html,
+}
+
+impl ReportFormat {
+    fn formatter(self) -> &'static dyn Formatter {
+        match self {
+            ReportFormat::Readme => &Readme,
+            ReportFormat::Csv => &Csv,
+            ReportFormat::Json => &Json,
+            ReportFormat::Html => &Html,
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = ParseReportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "readme" => Ok(ReportFormat::Readme),
+            "csv" => Ok(ReportFormat::Csv),
+            "json" => Ok(ReportFormat::Json),
+            "html" => Ok(ReportFormat::Html),
+            _ => Err(ParseReportError(format!(
+                "unknown report format `{s}`, expecting one of `readme`, `csv`, `json` or `html`"
+            ))),
+        }
+    }
+}
+
+impl Display for ReportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportFormat::Readme => f.write_str("readme"),
+            ReportFormat::Csv => f.write_str("csv"),
+            ReportFormat::Json => f.write_str("json"),
+            ReportFormat::Html => f.write_str("html"),
+        }
+    }
+}
+
+/// A report to write, parsed from `<format>:<path>`. The path of the `readme` format defaults to `README.md`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub format: ReportFormat,
+    pub path: String,
+}
+
+impl Default for Report {
+    fn default() -> Self {
+        Self {
+            format: ReportFormat::Readme,
+            path: "README.md".into(),
+        }
+    }
+}
+
+impl Report {
+    /// Renders the report and writes it to its path.
+    pub fn write(&self, timings: &[Timings], total_millis: f64) -> Result<(), Error> {
+        let existing = match fs::read_to_string(&self.path) {
+            Ok(existing) => Some(existing),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(Error::IO(e)),
+        };
+
+        let contents =
+            self.format
+                .formatter()
+                .render(timings, total_millis, existing.as_deref())?;
+
+        if let Some(parent) = Path::new(&self.path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&self.path, contents)?;
+        Ok(())
+    }
+}
+
+impl FromStr for Report {
+    type Err = ParseReportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((format, path)) if !path.is_empty() => Ok(Self {
+                format: format.parse()?,
+                path: path.into(),
+            }),
+            Some(_) => Err(ParseReportError(format!("missing path in report `{s}`"))),
+            None => match s.parse()? {
+                ReportFormat::Readme => Ok(Self::default()),
+                _ => Err(ParseReportError(format!(
+                    "missing path in report `{s}`, expecting `<format>:<path>`"
+                ))),
+            },
+        }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.format, self.path)
+    }
+}
+
+/// An error which can be returned when parsing a [`Report`].
+#[derive(Debug)]
+pub struct ParseReportError(String);
+
+impl std::error::Error for ParseReportError {}
+
+impl Display for ParseReportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The solved parts of all days, along with their duration and heap usage. The parse phase is part [`PARSE`].
+fn parts(
+    timings: &[Timings],
+) -> impl Iterator<Item = (&Timings, u8, Duration, Option<MemoryStats>)> {
+    timings.iter().flat_map(|timing| {
+        [
+            (PARSE, timing.parse, None),
+            (1, timing.part_1, timing.part_1_memory),
+            (2, timing.part_2, timing.part_2_memory),
+        ]
+        .into_iter()
+        .filter_map(move |(part, duration, memory)| Some((timing, part, duration?, memory)))
+    })
+}
+
+/// One row per solved part.
+pub struct Csv;
+
+impl Formatter for Csv {
+    fn render(&self, timings: &[Timings], _: f64, _: Option<&str>) -> Result<String, Error> {
+        let mut lines = vec!["year,day,part,duration_nanos,peak_bytes,allocations".to_string()];
+
+        for (timing, part, duration, memory) in parts(timings) {
+            lines.push(format!(
+                "{},{},{part},{},{},{}",
+                timing.puzzle.year,
+                timing.puzzle.day.into_inner(),
+                duration.as_nanos(),
+                memory.map(|x| x.peak_bytes.to_string()).unwrap_or_default(),
+                memory
+                    .map(|x| x.allocations.to_string())
+                    .unwrap_or_default(),
+            ));
+        }
+
+        lines.push(String::new());
+        Ok(lines.join("\n"))
+    }
+}
+
+/// An object with the total time and one entry per solved part.
+pub struct Json;
+
+impl Formatter for Json {
+    #[allow(clippy::cast_precision_loss)]
+    fn render(
+        &self,
+        timings: &[Timings],
+        total_millis: f64,
+        _: Option<&str>,
+    ) -> Result<String, Error> {
+        let parts: Vec<JsonValue> = parts(timings)
+            .map(|(timing, part, duration, memory)| {
+                let mut object: HashMap<String, JsonValue> = HashMap::new();
+                object.insert("year".into(), f64::from(timing.puzzle.year).into());
+                object.insert(
+                    "day".into(),
+                    f64::from(timing.puzzle.day.into_inner()).into(),
+                );
+                object.insert("part".into(), f64::from(part).into());
+                object.insert("duration_nanos".into(), (duration.as_nanos() as f64).into());
+                object.insert(
+                    "peak_bytes".into(),
+                    memory.map_or(JsonValue::Null, |x| (x.peak_bytes as f64).into()),
+                );
+                object.insert(
+                    "allocations".into(),
+                    memory.map_or(JsonValue::Null, |x| (x.allocations as f64).into()),
+                );
+                object.into()
+            })
+            .collect();
+
+        let mut object: HashMap<String, JsonValue> = HashMap::new();
+        object.insert("total_millis".into(), total_millis.into());
+        object.insert("parts".into(), parts.into());
+
+        JsonValue::from(object)
+            .format()
+            .map(|mut json| {
+                json.push('\n');
+                json
+            })
+            .map_err(|e| Error::Parser(e.to_string()))
+    }
+}
+
+/// A standalone page with the same table as the readme.
+pub struct Html;
+
+impl Formatter for Html {
+    fn render(
+        &self,
+        timings: &[Timings],
+        total_millis: f64,
+        _: Option<&str>,
+    ) -> Result<String, Error> {
+        let title = timings.first().map_or_else(
+            || "Benchmarks".to_string(),
+            |x| format!("Advent of Code {} Benchmarks", x.puzzle.year),
+        );
+        let has_parse = timings.iter().any(|x| x.parse.is_some());
+        let has_memory = timings
+            .iter()
+            .any(|x| x.part_1_memory.is_some() || x.part_2_memory.is_some());
+
+        let mut rows = vec![];
+
+        let mut header = "<tr><th>Day</th>".to_string();
+        if has_parse {
+            header.push_str("<th>Parse</th>");
+        }
+        header.push_str("<th>Part 1</th><th>Part 2</th>");
+        if has_memory {
+            header.push_str("<th>Peak memory</th><th>Allocations</th>");
+        }
+        header.push_str("</tr>");
+        rows.push(header);
+
+        for timing in timings {
+            // unlike the readme, the page can be written anywhere, so days are not linked to their solutions.
+            let mut row = format!("<tr><td>Day {}</td>", timing.puzzle.day.into_inner());
+
+            if has_parse {
+                row.push_str(&format!("<td>{}</td>", format_timing(timing.parse)));
+            }
+
+            row.push_str(&format!(
+                "<td>{}</td><td>{}</td>",
+                format_timing(timing.part_1),
+                format_timing(timing.part_2),
+            ));
+
+            if has_memory {
+                row.push_str(&format!(
+                    "<td>{}</td><td>{}</td>",
+                    memory_cell(timing, |x| format_bytes(x.peak_bytes)),
+                    memory_cell(timing, |x| x.allocations.to_string())
+                ));
+            }
+
+            row.push_str("</tr>");
+            rows.push(row);
+        }
+
+        Ok(format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ font-family: sans-serif; margin: 2rem; }}
+table {{ border-collapse: collapse; }}
+th, td {{ border: 1px solid #ccc; padding: 0.25rem 0.75rem; text-align: center; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<table>
+{}
+</table>
+<p><strong>Total: {total_millis:.2}ms</strong></p>
+</body>
+</html>
+"#,
+            rows.join("\n")
+        ))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Csv, Formatter, Html, Json, Report, ReportFormat};
+    use crate::puzzle;
+    use crate::template::alloc::MemoryStats;
+    use crate::template::readme_benchmarks::Timings;
+
+    fn get_mock_timings() -> Vec<Timings> {
+        vec![Timings {
+            puzzle: puzzle!(2023, 1),
+            parse: Some(Duration::from_micros(500)),
+            part_1: Some(Duration::from_micros(1500)),
+            part_2: None,
+            part_1_memory: Some(MemoryStats {
+                peak_bytes: 2048,
+                allocations: 3,
+            }),
+            part_2_memory: None,
+            total_nanos: 1.5e6,
+        }]
+    }
+
+    #[test]
+    fn parses_reports() {
+        let report: Report = "csv:target/bench.csv".parse().unwrap();
+        assert_eq!(report.format, ReportFormat::Csv);
+        assert_eq!(report.path, "target/bench.csv");
+        assert_eq!("readme".parse::<Report>().unwrap(), Report::default());
+        assert!("csv".parse::<Report>().is_err());
+        assert!("xml:bench.xml".parse::<Report>().is_err());
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = Csv.render(&get_mock_timings(), 1.5, None).unwrap();
+        assert_eq!(
+            csv,
+            "year,day,part,duration_nanos,peak_bytes,allocations\n2023,1,0,500000,,\n2023,1,1,1500000,2048,3\n"
+        );
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = Json.render(&get_mock_timings(), 1.5, None).unwrap();
+        let value: tinyjson::JsonValue = json.parse().unwrap();
+        assert_eq!(value["total_millis"], tinyjson::JsonValue::Number(1.5));
+        assert_eq!(value["parts"][0]["part"], 0_f64.into());
+        assert_eq!(value["parts"][0]["peak_bytes"], tinyjson::JsonValue::Null);
+        assert_eq!(value["parts"][1]["duration_nanos"], 1_500_000_f64.into());
+        assert_eq!(value["parts"][1]["peak_bytes"], 2048_f64.into());
+    }
+
+    #[test]
+    fn renders_html() {
+        let html = Html.render(&get_mock_timings(), 1.5, None).unwrap();
+        assert!(html.contains("<title>Advent of Code 2023 Benchmarks</title>"));
+        assert!(html.contains(
+            "<tr><td>Day 1</td><td>500.0µs</td><td>1.5ms</td><td>-</td><td>2.0KB / -</td><td>3 / -</td></tr>"
+        ));
+    }
+}
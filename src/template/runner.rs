@@ -1,63 +1,410 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::alloc::{self, format_bytes, MemoryStats};
+use crate::template::error::{format_error, indent};
+use crate::template::input::InputSource;
+use crate::template::record::{part_label, OutputFormat, Record, Status, PARSE};
+use crate::template::stats::Stats;
+use crate::template::submissions::{self, Verdict};
+use crate::template::{
+    answers, aoc_client, visualize, ANSI_GREEN, ANSI_ITALIC, ANSI_RED, ANSI_RESET,
+};
+use crate::PuzzleId;
+use std::error::Error;
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Upper bound for the number of benchmark samples, unless `--min-samples` asks for more.
+const MAX_SAMPLES: u128 = 10000;
+
+/// Controls how long a solution part is benchmarked for when running with `--time`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchOptions {
+    /// Approximate time budget for the measured iterations.
+    pub bench_time: Duration,
+    /// Minimum number of measured iterations, regardless of the time budget.
+    pub min_samples: u128,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            bench_time: Duration::from_secs(1),
+            min_samples: 10,
+        }
+    }
+}
+
+impl BenchOptions {
+    /// Reads `--bench-time <seconds>` and `--min-samples <n>` from the arguments passed to a solution binary.
+    #[must_use]
+    pub fn from_args(args: &[String]) -> Self {
+        let value = |name: &str| {
+            args.iter()
+                .position(|x| x == name)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        let default = Self::default();
+
+        Self {
+            bench_time: value("--bench-time")
+                .and_then(|x| parse_bench_time(x).ok())
+                .unwrap_or(default.bench_time),
+            min_samples: value("--min-samples")
+                .and_then(|x| x.parse().ok())
+                .unwrap_or(default.min_samples),
+        }
+    }
+
+    /// Converts the options back into arguments that can be forwarded to a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--bench-time".into(),
+            self.bench_time.as_secs_f64().to_string(),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+        ]
+    }
+
+    /// Number of measured iterations for a function that took `base_time` on its first run.
+    fn iterations(&self, base_time: &Duration) -> u128 {
+        let fitting = self.bench_time.as_nanos() / base_time.as_nanos().max(10);
+        fitting.clamp(self.min_samples, MAX_SAMPLES.max(self.min_samples))
+    }
+}
+
+/// Parses a benchmark time budget given in (fractional) seconds.
+pub fn parse_bench_time(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| format!("invalid bench time `{s}`, expecting a positive number of seconds"))
+}
+
+/// Return types of solution parts: `Option<T>`, or `Result<Option<T>, E>` for parts that can fail.
+pub trait PartOutput {
+    /// Converts the output into the answer, [`None`] if the part is not solved yet.
+    fn into_answer(self) -> Result<Option<String>, Box<dyn Error>>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn into_answer(self) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(self.map(|x| x.to_string()))
+    }
+}
+
+impl<T: Display, E: Into<Box<dyn Error>>> PartOutput for Result<Option<T>, E> {
+    fn into_answer(self) -> Result<Option<String>, Box<dyn Error>> {
+        self.map(|x| x.map(|x| x.to_string())).map_err(Into::into)
+    }
+}
+
+/// Reads `--time` and its options from the arguments passed to a solution binary.
+fn get_bench_options(args: &[String]) -> Option<BenchOptions> {
+    args.iter()
+        .any(|x| x == "--time")
+        .then(|| BenchOptions::from_args(args))
+}
+
+/// Parses the input of a solution binary and prints the time the parse phase took, see [`run_parse`].
+/// If parsing fails, the error is printed along with both parts as not run, and [`None`] is returned.
+pub fn parse_input<P, E: Into<Box<dyn Error>>>(
+    parse: impl Fn(&str) -> Result<P, E>,
+    input: &str,
+    puzzle: PuzzleId,
+) -> Option<P> {
+    let args: Vec<String> = env::args().collect();
+    let format = get_output_format(&args);
+    let bench = get_bench_options(&args);
+
+    let (parsed, record) = run_parse(
+        parse,
+        input,
+        puzzle,
+        bench.as_ref(),
+        format == OutputFormat::Text,
+    );
+
+    let skipped = match parsed {
+        Some(_) => vec![],
+        None => skipped_parts(puzzle).to_vec(),
+    };
+
+    for record in std::iter::once(&record).chain(&skipped) {
+        match format {
+            OutputFormat::Text => print_record(record),
+            OutputFormat::Json => println!("{}", record.to_json()),
+        }
+    }
+
+    visualize::present(PARSE);
+
+    parsed
+}
+
+/// Records of both parts of a solution whose parse phase failed. The parts did not run, so they have no samples.
+#[must_use]
+pub fn skipped_parts(puzzle: PuzzleId) -> [Record; 2] {
+    [1, 2].map(|part| Record {
+        puzzle,
+        part,
+        answer: None,
+        duration: Duration::ZERO,
+        samples: 0,
+        status: Status::Error,
+        stats: None,
+        verified: None,
+        error: Some("not run, parse failed".into()),
+        memory: None,
+    })
+}
+
+pub fn run_part<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) {
+    let args: Vec<String> = env::args().collect();
+    let format = get_output_format(&args);
+    let bench = get_bench_options(&args);
+
+    let mut record = run(
+        func,
+        input,
+        puzzle,
+        part,
+        bench.as_ref(),
+        format == OutputFormat::Text,
+    );
+
+    // confirmed answers belong to the puzzle input, other inputs have different answers.
+    let is_puzzle_input = InputSource::from_args(&args).is_puzzle_input();
+    if is_puzzle_input {
+        answers::verify(&mut record);
+    }
+
+    match format {
+        OutputFormat::Text => print_record(&record),
+        OutputFormat::Json => println!("{}", record.to_json()),
+    }
+
+    visualize::present(part);
+
+    if let Some(answer) = &record.answer {
+        submit_result(answer, puzzle, part, is_puzzle_input);
+    }
+}
+
+/// Runs a solution part and returns its record. The part is executed once, or benched if `bench` options are passed
+/// (approx. `--bench-time` seconds of execution time or `--min-samples` samples, whatever take longer.)
+/// If `progress` is set, the answer is printed as soon as it is available, ahead of the final output of `print_record`.
+/// Parts that return an error are not benched, the record carries the formatted error chain instead of an answer.
+/// With the `alloc_stats` feature, the heap usage of the first execution is recorded as well.
+pub fn run<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    bench: Option<&BenchOptions>,
+    progress: bool,
+) -> Record {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, base_time, memory, stats, samples) =
+        run_timed(func, input, bench, progress, |result| {
+            if progress {
+                print_result(result, &part_str);
+            }
+        });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let (answer, status, error) = match result {
+        Ok(Some(answer)) => (Some(answer), Status::Solved, None),
+        Ok(None) => (None, Status::Unsolved, None),
+        Err(e) => (None, Status::Error, Some(format_error(&*e))),
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    Record {
+        puzzle,
+        part,
+        answer,
+        duration: stats.map_or(base_time, |stats| stats.median),
+        samples,
+        status,
+        stats,
+        verified: None,
+        error,
+        memory,
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+/// Runs the parse phase of a solution and returns the parsed input along with the record of the phase.
+/// The record has no answer, it is benched like a part if `bench` options are passed.
+/// If parsing fails, no input is returned and the record carries the formatted error chain instead.
+pub fn run_parse<P, E: Into<Box<dyn Error>>>(
+    parse: impl Fn(&str) -> Result<P, E>,
+    input: &str,
+    puzzle: PuzzleId,
+    bench_options: Option<&BenchOptions>,
+    progress: bool,
+) -> (Option<P>, Record) {
+    let ((result, base_time), memory) = alloc::measure(|| {
+        let timer = Instant::now();
+        let result = parse(input);
+        (result, timer.elapsed())
+    });
+
+    let mut record = Record {
+        puzzle,
+        part: PARSE,
+        answer: None,
+        duration: base_time,
+        samples: 1,
+        status: Status::Solved,
+        stats: None,
+        verified: None,
+        error: None,
+        memory,
+    };
+
+    let parsed = match result {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            record.status = Status::Error;
+            record.error = Some(format_error(&*e.into()));
+            return (None, record);
+        }
+    };
+
+    if progress && bench_options.is_some() {
+        print!("{}:", part_label(PARSE));
+    }
+
+    if let Some(options) = bench_options {
+        let (stats, samples) = bench(&parse, input, &base_time, options, progress);
+        record.duration = stats.as_ref().map_or(base_time, |stats| stats.median);
+        record.samples = samples;
+        record.stats = stats;
+    }
+
+    (Some(parsed), record)
+}
+
+/// Prints the result of a solution part, including benchmark statistics if present.
+pub fn print_record(record: &Record) {
+    print!("\r{}", format_record(record));
+}
+
+/// Formats the result of a solution part as it is printed by `print_record`, with a trailing newline.
+#[must_use]
+pub fn format_record(record: &Record) -> String {
+    let part = part_label(record.part);
+    let duration_str = format_duration(&record.duration, record.samples);
+    let verified_str = format_verified(record);
+    let memory_str = record.memory.as_ref().map_or(String::new(), format_memory);
+
+    if let Some(error) = &record.error {
+        let label = match record.status {
+            Status::Timeout => "timeout",
+            Status::OutOfMemory => "out of memory",
+            _ => "error",
+        };
+        return format!(
+            "{part}: {ANSI_RED}✖ {label}{ANSI_RESET}{duration_str}\n{ANSI_RED}{}{ANSI_RESET}\n",
+            indent(error)
+        );
+    }
+
+    let mut s = match &record.answer {
+        None if record.part == PARSE => format!("{part}:{duration_str}{memory_str}\n"),
+        Some(answer) if answer.contains('\n') => {
+            format!("{part}: ▼{verified_str}{duration_str}{memory_str}\n{answer}\n")
+        }
+        Some(answer) => {
+            format!(
+                "{part}: {ANSI_BOLD}{answer}{ANSI_RESET}{verified_str}{duration_str}{memory_str}\n"
+            )
+        }
+        None => format!("{part}: ✖{verified_str}             \n"),
+    };
+
+    if let (Some(stats), Status::Solved) = (&record.stats, record.status) {
+        s.push_str(&format_stats(stats));
+        s.push('\n');
+    }
+
+    s
+}
+
+/// Reads the `--format` argument passed to the solution binary, defaulting to text.
+fn get_output_format(args: &[String]) -> OutputFormat {
+    args.iter()
+        .position(|x| x == "--format")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse().ok())
+        .unwrap_or_default()
+}
+
+type PartResult = Result<Option<String>, Box<dyn Error>>;
+
+fn run_timed<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+    bench_options: Option<&BenchOptions>,
+    progress: bool,
+    hook: impl Fn(&PartResult),
+) -> (
+    PartResult,
+    Duration,
+    Option<MemoryStats>,
+    Option<Stats>,
+    u128,
+) {
+    let ((output, base_time), memory) = alloc::measure(|| {
+        let timer = Instant::now();
+        let output = func(input.clone());
+        (output, timer.elapsed())
+    });
 
+    let result = output.into_answer();
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
+    // there is nothing to measure if the part failed.
+    let bench_options = bench_options.filter(|_| result.is_ok());
 
-    (result, run.0, run.1)
+    if let Some(options) = bench_options {
+        let (stats, samples) = bench(func, input, &base_time, options, progress);
+        (result, base_time, memory, stats, samples)
+    } else {
+        (result, base_time, memory, None, 1)
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    options: &BenchOptions,
+    progress: bool,
+) -> (Option<Stats>, u128) {
     let mut stdout = stdout();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    if progress {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations = options.iterations(base_time);
+
+    // warm up caches and the allocator before measuring.
+    for _ in 0..(bench_iterations / 10).clamp(1, 1000) {
+        func(input.clone());
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,72 +416,64 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    (Stats::from_samples(&timers), bench_iterations)
 }
 
+/// Formats the duration of a part, parts that did not run (zero samples) have none.
 fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
+    if samples == 0 {
+        String::new()
+    } else if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({duration:.1?} median @ {samples} samples)")
+    }
+}
+
+/// Marks an answer as matching (✔) or not matching (✖) the confirmed answer of the part.
+fn format_verified(record: &Record) -> String {
+    match record.verified {
+        Some(true) => format!(" {ANSI_GREEN}✔{ANSI_RESET}"),
+        Some(false) => format!(" {ANSI_RED}✖ wrong answer{ANSI_RESET}"),
+        None => String::new(),
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
-    let is_intermediate_result = duration_str.is_empty();
+fn format_memory(memory: &MemoryStats) -> String {
+    format!(
+        " {ANSI_ITALIC}[{} peak · {} allocations]{ANSI_RESET}",
+        format_bytes(memory.peak_bytes),
+        memory.allocations
+    )
+}
 
+fn format_stats(stats: &Stats) -> String {
+    format!(
+        "  {ANSI_ITALIC}min {:.1?} · mean {:.1?} · p95 {:.1?} · σ {:.1?} · {} outlier(s){ANSI_RESET}",
+        stats.min, stats.mean, stats.p95, stats.std_dev, stats.outliers
+    )
+}
+
+/// Prints the answer of a part while it is still being benched, `print_record` later overwrites this line.
+fn print_result(result: &PartResult, part: &str) {
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
-        }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
-        }
+        Ok(Some(result)) if result.contains('\n') => print!("{part}: ▼ "),
+        Ok(Some(result)) => print!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}"),
+        Ok(None) => print!("{part}: ✖"),
+        Err(_) => print!("{part}: {ANSI_RED}✖ error{ANSI_RESET}"),
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. the answer was computed from the puzzle input.
+///  3. the answer is not known to be wrong from previous submissions.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+    is_puzzle_input: bool,
+) -> Option<Verdict> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,11 +496,122 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let answer = result.to_string();
+
+    if !is_puzzle_input {
+        eprintln!("Refusing to submit {answer}: it was not computed from the puzzle input.");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let previous = submissions::read(puzzle).unwrap_or_else(|e| {
+        eprintln!("Could not read \"{}\": {e}", submissions::get_path(puzzle));
+        process::exit(1);
+    });
+
+    if let Err(refusal) = submissions::check(&previous, part, &answer) {
+        eprintln!("Refusing to submit {answer}: {refusal}");
+        process::exit(1);
+    }
+
+    println!("Submitting result...");
+    let verdict = aoc_client::submit(puzzle, part, &answer).unwrap_or_else(|e| {
+        eprintln!("Failed to submit result: {e}");
+        process::exit(1);
+    });
+
+    if let Err(e) = submissions::record(puzzle, part, &answer, verdict) {
+        eprintln!("Could not record submission: {e}");
+    }
+
+    Some(verdict)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::num::ParseIntError;
+
+    use super::{format_record, run, run_parse};
+    use crate::puzzle;
+    use crate::template::error::InputError;
+    use crate::template::record::{Status, PARSE};
+
+    #[test]
+    fn runs_parse_phase() {
+        let puzzle = puzzle!(2023, 4);
+        let (parsed, record) = run_parse(
+            |input| Ok::<_, ParseIntError>(input.lines().map(str::len).collect::<Vec<_>>()),
+            "ab\nc",
+            puzzle,
+            None,
+            false,
+        );
+
+        let parsed = parsed.unwrap();
+        assert_eq!(parsed, vec![2, 1]);
+        assert_eq!(record.part, PARSE);
+        assert_eq!(record.status, Status::Solved);
+        assert_eq!(record.answer, None);
+        assert!(format_record(&record).starts_with("Parse: ("));
+
+        let record = run(
+            |x: &[usize]| Some(x.iter().sum::<usize>()),
+            &parsed[..],
+            puzzle,
+            1,
+            None,
+            false,
+        );
+        assert_eq!(record.answer.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        let (parsed, record) = run_parse(
+            |input| {
+                input
+                    .lines()
+                    .map(str::parse::<u32>)
+                    .collect::<Result<Vec<_>, _>>()
+            },
+            "1\nx",
+            puzzle!(2023, 4),
+            None,
+            false,
+        );
+
+        assert_eq!(parsed, None);
+        assert_eq!(record.part, PARSE);
+        assert_eq!(record.status, Status::Error);
+        assert_eq!(
+            record.error.as_deref(),
+            Some("invalid digit found in string")
+        );
+        assert!(format_record(&record).starts_with("Parse: \x1b[31m✖ error"));
+    }
+
+    #[test]
+    fn reports_part_errors() {
+        let record = run(
+            |input: &str| -> Result<Option<u32>, InputError> {
+                Err(InputError::new(1, "invalid operation")
+                    .with_column(6)
+                    .with_text(input)
+                    .with_source("expected `<label>=<focal length>` or `<label>-`, got `cm+2`"))
+            },
+            "rn=1,cm+2,qp=3",
+            puzzle!(2023, 15),
+            1,
+            None,
+            false,
+        );
+
+        assert_eq!(record.status, Status::Error);
+        assert_eq!(record.answer, None);
+
+        let output = format_record(&record);
+        assert!(output.starts_with("Part 1: \x1b[31m✖ error"), "{output}");
+        assert!(output.ends_with(
+            "\x1b[31m  line 1, column 6: invalid operation\n    | rn=1,cm+2,qp=3\n    |      ^\n  caused by: expected `<label>=<focal length>` or `<label>-`, got `cm+2`\x1b[0m\n"
+        ), "{output}");
+    }
 }
@@ -0,0 +1,107 @@
+/// Summary statistics over the samples collected while benchmarking a solution part.
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+    /// Number of samples outside of the 1.5 * IQR fences (Tukey's method).
+    pub outliers: u128,
+}
+
+impl Stats {
+    /// Computes statistics for the provided samples. Returns [`None`] if there are no samples.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = samples.iter().map(|x| x.as_nanos() as f64).collect();
+        nanos.sort_by(f64::total_cmp);
+
+        #[allow(clippy::cast_precision_loss)]
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+
+        let q1 = percentile(&nanos, 0.25);
+        let q3 = percentile(&nanos, 0.75);
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+        let outliers = nanos.iter().filter(|&&x| x < lower || x > upper).count();
+
+        Some(Stats {
+            mean: to_duration(mean),
+            min: to_duration(nanos[0]),
+            median: to_duration(percentile(&nanos, 0.5)),
+            p95: to_duration(percentile(&nanos, 0.95)),
+            std_dev: to_duration(variance.sqrt()),
+            outliers: outliers as u128,
+        })
+    }
+}
+
+/// Linear interpolation between the closest ranks of a sorted, non-empty slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - rank.floor())
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn empty_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42])).unwrap();
+        assert_eq!(stats.min, Duration::from_nanos(42));
+        assert_eq!(stats.median, Duration::from_nanos(42));
+        assert_eq!(stats.p95, Duration::from_nanos(42));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn median_is_not_skewed_by_outliers() {
+        let stats =
+            Stats::from_samples(&nanos(&[10, 12, 11, 10, 1000, 11, 12, 10, 11, 12])).unwrap();
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(11));
+        assert_eq!(stats.mean, Duration::from_nanos(110));
+        assert_eq!(stats.outliers, 1);
+    }
+
+    #[test]
+    fn percentiles_and_deviation() {
+        let samples: Vec<u64> = (1..=100).collect();
+        let stats = Stats::from_samples(&nanos(&samples)).unwrap();
+        assert_eq!(stats.median, Duration::from_nanos(51));
+        assert_eq!(stats.p95, Duration::from_nanos(95));
+        assert_eq!(stats.std_dev, Duration::from_nanos(29));
+        assert_eq!(stats.outliers, 0);
+    }
+}
@@ -0,0 +1,316 @@
+/// Module that keeps a log of submitted answers and the verdicts they received.
+/// The log is used to refuse submissions that are already known to be wrong, before they cost a timeout on the server.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{answers, get_data_path};
+use crate::PuzzleId;
+
+/// The response of the server to a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited,
+    /// The response could not be interpreted, e.g. because the part was already solved.
+    Unknown,
+}
+
+impl Verdict {
+    /// Interprets the text of the server response to a submission.
+    #[must_use]
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if response.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if response.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if response.contains("You gave an answer too recently") {
+            Verdict::RateLimited
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited => "rate_limited",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "correct" => Some(Verdict::Correct),
+            "too_high" => Some(Verdict::TooHigh),
+            "too_low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            "rate_limited" => Some(Verdict::RateLimited),
+            "unknown" => Some(Verdict::Unknown),
+            _ => None,
+        }
+    }
+
+    /// Whether the verdict tells that the answer is not correct.
+    pub(crate) fn is_wrong(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.as_str().replace('_', " "))
+    }
+}
+
+/// A submitted answer and the verdict it received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl Submission {
+    fn to_json(&self) -> String {
+        let mut object: HashMap<String, JsonValue> = HashMap::new();
+        object.insert("part".into(), f64::from(self.part).into());
+        object.insert("answer".into(), self.answer.clone().into());
+        object.insert("verdict".into(), self.verdict.as_str().to_string().into());
+        JsonValue::from(object).stringify().unwrap()
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn from_json(line: &str) -> Option<Self> {
+        let value: JsonValue = line.parse().ok()?;
+        let object: &HashMap<String, JsonValue> = value.get()?;
+
+        Some(Submission {
+            part: *object.get("part")?.get::<f64>()? as u8,
+            answer: object.get("answer")?.get::<String>()?.clone(),
+            verdict: Verdict::parse(object.get("verdict")?.get::<String>()?)?,
+        })
+    }
+}
+
+/// Reasons to refuse a submission.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    KnownWrong(Verdict),
+    NotBelow(String),
+    NotAbove(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Refusal::KnownWrong(verdict) => {
+                write!(f, "this answer was already submitted and was {verdict}.")
+            }
+            Refusal::NotBelow(bound) => {
+                write!(
+                    f,
+                    "the answer must be lower than {bound}, which was too high."
+                )
+            }
+            Refusal::NotAbove(bound) => {
+                write!(
+                    f,
+                    "the answer must be higher than {bound}, which was too low."
+                )
+            }
+        }
+    }
+}
+
+/// Checks an answer against previous submissions of the same part.
+pub fn check(submissions: &[Submission], part: u8, answer: &str) -> Result<(), Refusal> {
+    let submissions: Vec<&Submission> = submissions.iter().filter(|x| x.part == part).collect();
+
+    if let Some(correct) = submissions.iter().find(|x| x.verdict == Verdict::Correct) {
+        return Err(Refusal::AlreadySolved(correct.answer.clone()));
+    }
+
+    if let Some(known) = submissions
+        .iter()
+        .find(|x| x.answer == answer && x.verdict.is_wrong())
+    {
+        return Err(Refusal::KnownWrong(known.verdict));
+    }
+
+    let Ok(value) = answer.parse::<i128>() else {
+        return Ok(());
+    };
+
+    let bound = |verdict: Verdict| {
+        submissions
+            .iter()
+            .filter(move |x| x.verdict == verdict)
+            .filter_map(|x| x.answer.parse::<i128>().ok())
+    };
+
+    if let Some(upper) = bound(Verdict::TooHigh).min().filter(|x| value >= *x) {
+        return Err(Refusal::NotBelow(upper.to_string()));
+    }
+
+    if let Some(lower) = bound(Verdict::TooLow).max().filter(|x| value <= *x) {
+        return Err(Refusal::NotAbove(lower.to_string()));
+    }
+
+    Ok(())
+}
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId) -> String {
+    get_data_path("submissions", puzzle, "jsonl")
+}
+
+/// Reads all submissions of a day. A missing file means that nothing was submitted yet.
+pub fn read(puzzle: PuzzleId) -> Result<Vec<Submission>, io::Error> {
+    let path = get_path(puzzle);
+
+    if !Path::new(&path).exists() {
+        return Ok(vec![]);
+    }
+
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .filter_map(Submission::from_json)
+        .collect())
+}
+
+/// Appends a submission to the log of a day.
+pub fn append(puzzle: PuzzleId, submission: &Submission) -> Result<(), io::Error> {
+    let path = get_path(puzzle);
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    writeln!(file, "{}", submission.to_json())
+}
+
+/// Logs the verdict of a submission. Correct answers are also recorded as confirmed answers of the day.
+pub fn record(puzzle: PuzzleId, part: u8, answer: &str, verdict: Verdict) -> Result<(), io::Error> {
+    append(
+        puzzle,
+        &Submission {
+            part,
+            answer: answer.into(),
+            verdict,
+        },
+    )?;
+
+    if verdict == Verdict::Correct {
+        let mut confirmed = answers::read(puzzle)?;
+        confirmed.set(part, answer);
+        answers::write(puzzle, &confirmed)?;
+        println!("🎄 Recorded answer in \"{}\".", answers::get_path(puzzle));
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, Refusal, Submission, Verdict};
+
+    fn submission(part: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            part,
+            answer: answer.into(),
+            verdict,
+        }
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer. If you're stuck, ..."),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently; you have to wait."),
+            Verdict::RateLimited
+        );
+        assert_eq!(
+            Verdict::from_response("You don't seem to be solving the right level."),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let s = submission(2, "abc", Verdict::TooLow);
+        assert_eq!(Submission::from_json(&s.to_json()), Some(s));
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let log = [
+            submission(1, "10", Verdict::Wrong),
+            submission(1, "20", Verdict::RateLimited),
+            submission(2, "30", Verdict::Correct),
+        ];
+        assert_eq!(
+            check(&log, 1, "10"),
+            Err(Refusal::KnownWrong(Verdict::Wrong))
+        );
+        assert_eq!(check(&log, 1, "20"), Ok(()));
+        assert_eq!(
+            check(&log, 2, "31"),
+            Err(Refusal::AlreadySolved("30".into()))
+        );
+    }
+
+    #[test]
+    fn refuses_answers_out_of_bounds() {
+        let log = [
+            submission(1, "100", Verdict::TooHigh),
+            submission(1, "150", Verdict::TooHigh),
+            submission(1, "10", Verdict::TooLow),
+        ];
+        assert_eq!(
+            check(&log, 1, "100"),
+            Err(Refusal::KnownWrong(Verdict::TooHigh))
+        );
+        assert_eq!(check(&log, 1, "120"), Err(Refusal::NotBelow("100".into())));
+        assert_eq!(check(&log, 1, "-5"), Err(Refusal::NotAbove("10".into())));
+        assert_eq!(check(&log, 1, "50"), Ok(()));
+        assert_eq!(check(&log, 1, "abc"), Ok(()));
+        assert_eq!(check(&log, 2, "500"), Ok(()));
+    }
+}
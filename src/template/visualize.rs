@@ -0,0 +1,497 @@
+/// Frames for visual debugging, e.g. of grid puzzles. Solutions emit a [`Frame`] with [`frame`] whenever there is
+/// something to see. Frames are only recorded when a solution runs with `cargo solve <day> --visualize`, which builds it
+/// with the `visualize` feature: without the feature, [`frame`] compiles to nothing and normal runs pay nothing.
+/// The frames of a part are played back in the terminal once the part finished, or written to a directory of frame files.
+use std::{
+    borrow::Borrow,
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::record::PARSE;
+
+/// A color of a cell in a color grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+/// The cells of a frame, in rows from top to bottom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Grid {
+    Chars(Vec<Vec<char>>),
+    Colors(Vec<Vec<Rgb>>),
+}
+
+/// One step of a visualization: a grid with a caption.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub grid: Grid,
+    pub caption: String,
+}
+
+impl Frame {
+    /// A frame of characters, e.g. `Frame::chars(&grid)` for a `Vec<Vec<char>>`.
+    pub fn chars<R, C>(rows: R) -> Self
+    where
+        R: IntoIterator,
+        R::Item: IntoIterator<Item = C>,
+        C: Borrow<char>,
+    {
+        Self::new(Grid::Chars(
+            rows.into_iter()
+                .map(|row| row.into_iter().map(|c| *c.borrow()).collect())
+                .collect(),
+        ))
+    }
+
+    /// A frame of characters, one row per line of the text.
+    #[must_use]
+    pub fn text(text: &str) -> Self {
+        Self::chars(text.lines().map(str::chars))
+    }
+
+    /// A frame of `width` × `height` colored cells, `color` is called with the column and row of every cell.
+    pub fn colors(width: usize, height: usize, color: impl Fn(usize, usize) -> Rgb) -> Self {
+        Self::new(Grid::Colors(
+            (0..height)
+                .map(|y| (0..width).map(|x| color(x, y)).collect())
+                .collect(),
+        ))
+    }
+
+    #[must_use]
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    fn new(grid: Grid) -> Self {
+        Self {
+            grid,
+            caption: String::new(),
+        }
+    }
+
+    /// The extension of the file [`Frame::to_file`] renders: `txt` for characters and `ppm` for colors.
+    #[must_use]
+    pub fn extension(&self) -> &'static str {
+        match self.grid {
+            Grid::Chars(_) => "txt",
+            Grid::Colors(_) => "ppm",
+        }
+    }
+
+    /// Renders the frame as a file: characters as text below the caption, colors as a plain PPM image with the caption as comment.
+    #[must_use]
+    pub fn to_file(&self) -> String {
+        match &self.grid {
+            Grid::Chars(rows) => {
+                let mut s = String::new();
+                if !self.caption.is_empty() {
+                    let _ = writeln!(s, "{}", self.caption);
+                }
+                for row in rows {
+                    let _ = writeln!(s, "{}", row.iter().collect::<String>());
+                }
+                s
+            }
+            Grid::Colors(rows) => {
+                let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+                let mut s = "P3\n".to_string();
+                for line in self.caption.lines() {
+                    let _ = writeln!(s, "# {line}");
+                }
+                let _ = writeln!(s, "{width} {}\n255", rows.len());
+                for row in rows {
+                    let cells: Vec<String> = (0..width)
+                        .map(|x| {
+                            let Rgb(r, g, b) = row.get(x).copied().unwrap_or(Rgb(0, 0, 0));
+                            format!("{r} {g} {b}")
+                        })
+                        .collect();
+                    let _ = writeln!(s, "{}", cells.join("  "));
+                }
+                s
+            }
+        }
+    }
+}
+
+/// Where recorded frames go, selected by the arguments passed to a solution binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Visualize {
+    /// Plays the frames back in the terminal, selected with `--visualize`.
+    Terminal,
+    /// Writes one file per frame to a directory, selected with `--visualize-dir <dir>`.
+    Directory(PathBuf),
+}
+
+impl Visualize {
+    /// Reads `--visualize` or `--visualize-dir <dir>` from the arguments passed to a solution binary.
+    #[must_use]
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        let dir = args
+            .iter()
+            .position(|x| x == "--visualize-dir")
+            .and_then(|i| args.get(i + 1));
+
+        match dir {
+            Some(dir) => Some(Visualize::Directory(dir.into())),
+            None => args
+                .iter()
+                .any(|x| x == "--visualize")
+                .then_some(Visualize::Terminal),
+        }
+    }
+
+    /// Converts the selection back into arguments that can be forwarded to a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Visualize::Terminal => vec!["--visualize".into()],
+            Visualize::Directory(dir) => {
+                vec!["--visualize-dir".into(), dir.display().to_string()]
+            }
+        }
+    }
+}
+
+/// Records a frame. `frame` is only called while frames are recorded, so building the frame costs nothing otherwise.
+#[inline]
+pub fn frame(frame: impl FnOnce() -> Frame) {
+    #[cfg(feature = "visualize")]
+    if is_active() {
+        recorder::push(frame());
+    }
+
+    #[cfg(not(feature = "visualize"))]
+    drop(frame);
+}
+
+/// Whether frames are recorded, for solutions that need extra work to visualize.
+#[must_use]
+#[inline]
+pub fn is_active() -> bool {
+    #[cfg(feature = "visualize")]
+    return recorder::get_output().is_some();
+
+    #[cfg(not(feature = "visualize"))]
+    false
+}
+
+/// Plays back or writes the frames recorded while a part ran. Called by the runner once the part finished.
+pub fn present(part: u8) {
+    #[cfg(feature = "visualize")]
+    {
+        let frames = recorder::take();
+        if frames.is_empty() {
+            return;
+        }
+
+        let label = crate::template::record::part_label(part);
+
+        match recorder::get_output() {
+            Some(Visualize::Terminal) => player::play(&label, &frames),
+            Some(Visualize::Directory(dir)) => match write_frames(dir, part, &frames) {
+                Ok(()) => eprintln!(
+                    "Wrote {} frame(s) of {label} to \"{}\".",
+                    frames.len(),
+                    dir.display()
+                ),
+                Err(e) => eprintln!("Could not write frames to \"{}\": {e}", dir.display()),
+            },
+            None => {}
+        }
+    }
+
+    #[cfg(not(feature = "visualize"))]
+    let _ = part;
+}
+
+/// Writes frames to files named after the part and the frame number, e.g. `part1-0001.txt`.
+pub fn write_frames(dir: &Path, part: u8, frames: &[Frame]) -> Result<(), io::Error> {
+    fs::create_dir_all(dir)?;
+
+    let prefix = if part == PARSE {
+        "parse".to_string()
+    } else {
+        format!("part{part}")
+    };
+
+    for (i, frame) in frames.iter().enumerate() {
+        let path = dir.join(format!("{prefix}-{:04}.{}", i + 1, frame.extension()));
+        fs::write(path, frame.to_file())?;
+    }
+
+    Ok(())
+}
+
+/// Frames recorded by the running part.
+#[cfg(feature = "visualize")]
+mod recorder {
+    use std::{env, mem, sync::Mutex};
+
+    use once_cell::sync::Lazy;
+
+    use super::{Frame, Visualize};
+
+    static OUTPUT: Lazy<Option<Visualize>> =
+        Lazy::new(|| Visualize::from_args(&env::args().collect::<Vec<_>>()));
+
+    static FRAMES: Mutex<Vec<Frame>> = Mutex::new(Vec::new());
+
+    pub fn get_output() -> Option<&'static Visualize> {
+        OUTPUT.as_ref()
+    }
+
+    pub fn push(frame: Frame) {
+        FRAMES.lock().unwrap().push(frame);
+    }
+
+    pub fn take() -> Vec<Frame> {
+        mem::take(&mut *FRAMES.lock().unwrap())
+    }
+}
+
+/// Terminal playback with pause and step controls.
+#[cfg(feature = "visualize")]
+mod player {
+    use std::{
+        io::{stdout, IsTerminal, Stdout, Write},
+        time::Duration,
+    };
+
+    use crossterm::{
+        cursor::{Hide, MoveTo, Show},
+        event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+        queue,
+        style::{Color, Print, ResetColor, SetBackgroundColor},
+        terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+    };
+
+    use super::{Frame, Grid, Rgb};
+    use crate::template::{ANSI_ITALIC, ANSI_RESET};
+
+    const HELP: &str = "space: pause · ←/→: step · +/-: speed · q: quit";
+    const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+    /// Restores the terminal when playback ends, even if it ends with a panic.
+    struct Screen;
+
+    impl Screen {
+        fn enter(stdout: &mut Stdout) -> std::io::Result<Self> {
+            terminal::enable_raw_mode()?;
+            let screen = Self;
+            queue!(stdout, EnterAlternateScreen, Hide)?;
+            Ok(screen)
+        }
+    }
+
+    impl Drop for Screen {
+        fn drop(&mut self) {
+            let mut stdout = stdout();
+            let _ = queue!(stdout, Show, LeaveAlternateScreen);
+            let _ = stdout.flush();
+            let _ = terminal::disable_raw_mode();
+        }
+    }
+
+    pub fn play(label: &str, frames: &[Frame]) {
+        let mut stdout = stdout();
+
+        // without a terminal, e.g. when piped to a file, the frames are printed one after another.
+        if !stdout.is_terminal() {
+            for (i, frame) in frames.iter().enumerate() {
+                println!("{}", title(label, i, frames.len(), frame));
+                for line in lines(frame, usize::MAX) {
+                    println!("{line}");
+                }
+            }
+            return;
+        }
+
+        if let Err(e) = run(&mut stdout, label, frames) {
+            eprintln!("Could not play back frames: {e}");
+        }
+    }
+
+    fn run(stdout: &mut Stdout, label: &str, frames: &[Frame]) -> std::io::Result<()> {
+        let _screen = Screen::enter(stdout)?;
+
+        let mut index = 0;
+        let mut paused = false;
+        let mut delay = DEFAULT_DELAY;
+
+        loop {
+            let is_last = index + 1 == frames.len();
+            draw(stdout, label, index, frames, paused || is_last)?;
+
+            let timeout = if paused || is_last { None } else { Some(delay) };
+
+            match next_key(timeout)? {
+                None => index += 1,
+                Some((KeyCode::Char('q') | KeyCode::Esc, _)) => return Ok(()),
+                Some((KeyCode::Char('c'), KeyModifiers::CONTROL)) => return Ok(()),
+                Some((KeyCode::Char(' '), _)) => paused = !paused,
+                Some((KeyCode::Right | KeyCode::Char('l'), _)) => {
+                    paused = true;
+                    index = (index + 1).min(frames.len() - 1);
+                }
+                Some((KeyCode::Left | KeyCode::Char('h'), _)) => {
+                    paused = true;
+                    index = index.saturating_sub(1);
+                }
+                Some((KeyCode::Home, _)) => index = 0,
+                Some((KeyCode::End, _)) => index = frames.len() - 1,
+                Some((KeyCode::Char('+'), _)) => delay = (delay / 2).max(Duration::from_millis(1)),
+                Some((KeyCode::Char('-'), _)) => delay = (delay * 2).min(Duration::from_secs(2)),
+                Some(_) => {}
+            }
+        }
+    }
+
+    /// Waits for a key press, up to `timeout` if set. Returns [`None`] once the timeout passed.
+    fn next_key(timeout: Option<Duration>) -> std::io::Result<Option<(KeyCode, KeyModifiers)>> {
+        loop {
+            if let Some(timeout) = timeout {
+                if !event::poll(timeout)? {
+                    return Ok(None);
+                }
+            }
+
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Release {
+                    return Ok(Some((key.code, key.modifiers)));
+                }
+            }
+        }
+    }
+
+    fn title(label: &str, index: usize, count: usize, frame: &Frame) -> String {
+        let mut title = format!("{label} · frame {}/{count}", index + 1);
+        if !frame.caption.is_empty() {
+            title.push_str(" · ");
+            title.push_str(&frame.caption);
+        }
+        title
+    }
+
+    /// Draws a frame, cropped to the size of the terminal.
+    fn draw(
+        stdout: &mut Stdout,
+        label: &str,
+        index: usize,
+        frames: &[Frame],
+        paused: bool,
+    ) -> std::io::Result<()> {
+        let frame = &frames[index];
+        let (columns, rows) = terminal::size()?;
+        let (columns, rows) = (usize::from(columns), usize::from(rows));
+        let grid_rows = rows.saturating_sub(2);
+
+        let mut title = title(label, index, frames.len(), frame);
+        if paused {
+            title.push_str(" · paused");
+        }
+
+        queue!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+        queue!(stdout, Print(crop(&title, columns)))?;
+
+        for (y, line) in lines(frame, columns).take(grid_rows).enumerate() {
+            queue!(stdout, MoveTo(0, to_u16(y + 1)), Print(line))?;
+        }
+
+        queue!(
+            stdout,
+            MoveTo(0, to_u16(rows.saturating_sub(1))),
+            Print(format!("{ANSI_ITALIC}{}{ANSI_RESET}", crop(HELP, columns)))
+        )?;
+
+        stdout.flush()
+    }
+
+    /// The rows of a frame as terminal lines, cropped to `columns`.
+    /// Color cells are two columns wide, so they are about square.
+    fn lines(frame: &Frame, columns: usize) -> Box<dyn Iterator<Item = String> + '_> {
+        match &frame.grid {
+            Grid::Chars(cells) => Box::new(
+                cells
+                    .iter()
+                    .map(move |row| row.iter().take(columns).collect()),
+            ),
+            Grid::Colors(cells) => Box::new(cells.iter().map(move |row| {
+                let mut line = String::new();
+                for &Rgb(r, g, b) in row.iter().take(columns / 2) {
+                    line.push_str(&SetBackgroundColor(Color::Rgb { r, g, b }).to_string());
+                    line.push_str("  ");
+                }
+                line.push_str(&ResetColor.to_string());
+                line
+            })),
+        }
+    }
+
+    fn crop(s: &str, columns: usize) -> String {
+        s.chars().take(columns).collect()
+    }
+
+    fn to_u16(x: usize) -> u16 {
+        u16::try_from(x).unwrap_or(u16::MAX)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::{write_frames, Frame, Rgb, Visualize};
+    use crate::template::record::PARSE;
+
+    #[test]
+    fn builds_frames() {
+        let frame = Frame::text("#.\n.#").with_caption("step 1");
+        assert_eq!(
+            frame,
+            Frame::chars(vec![vec!['#', '.'], vec!['.', '#']]).with_caption("step 1")
+        );
+        assert_eq!(frame.to_file(), "step 1\n#.\n.#\n");
+
+        let frame = Frame::colors(2, 1, |x, _| Rgb(255 * u8::from(x == 0), 0, 0));
+        assert_eq!(frame.extension(), "ppm");
+        assert_eq!(frame.to_file(), "P3\n2 1\n255\n255 0 0  0 0 0\n");
+    }
+
+    #[test]
+    fn parses_arguments() {
+        let args = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
+        assert_eq!(Visualize::from_args(&args("01 --release")), None);
+        assert_eq!(
+            Visualize::from_args(&args("01 --visualize")),
+            Some(Visualize::Terminal)
+        );
+        let dir = Visualize::from_args(&args("01 --visualize-dir target/frames")).unwrap();
+        assert_eq!(dir, Visualize::Directory("target/frames".into()));
+        assert_eq!(Visualize::from_args(&dir.to_args()), Some(dir));
+    }
+
+    #[test]
+    fn writes_frames() {
+        let dir = env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        let frames = [Frame::text("a"), Frame::text("b").with_caption("last")];
+
+        write_frames(&dir, 1, &frames).unwrap();
+        write_frames(&dir, PARSE, &frames[..1]).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.join("part1-0001.txt")).unwrap(),
+            "a\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("part1-0002.txt")).unwrap(),
+            "last\nb\n"
+        );
+        assert!(dir.join("parse-0001.txt").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,132 @@
+/// Detects changes to the files a solution depends on, for `cargo solve --watch`.
+/// Files are polled for their modification time, which keeps the template free of platform-specific watchers.
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::PuzzleId;
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The modification times of the watched files of a puzzle.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Snapshot(HashMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    /// Takes a snapshot of the watched files: the module of the puzzle, the shared library sources,
+    /// the data files of the day, e.g. `data/2023/inputs/01.txt` or `data/2023/examples/01.answers`,
+    /// and the file passed with `--input`, if any.
+    #[must_use]
+    pub fn take(puzzle: PuzzleId, input: Option<&Path>) -> Self {
+        let mut files = vec![PathBuf::from(format!("src/bin/{}.rs", puzzle.bin_name()))];
+        collect_sources(Path::new("src"), &mut files);
+        files.extend(input.map(Path::to_path_buf));
+
+        if let Ok(folders) = fs::read_dir(format!("data/{}", puzzle.year)) {
+            for folder in folders.flatten() {
+                let Ok(entries) = fs::read_dir(folder.path()) else {
+                    continue;
+                };
+                files.extend(
+                    entries
+                        .flatten()
+                        .map(|entry| entry.path())
+                        .filter(|path| is_data_file(path, puzzle)),
+                );
+            }
+        }
+
+        Self(
+            files
+                .into_iter()
+                .filter_map(|path| {
+                    let modified = fs::metadata(&path).and_then(|x| x.modified()).ok()?;
+                    Some((path, modified))
+                })
+                .collect(),
+        )
+    }
+
+    /// Blocks until a watched file of the puzzle was changed, added or removed since the snapshot.
+    pub fn wait_for_change(&self, puzzle: PuzzleId, input: Option<&Path>) {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            if Self::take(puzzle, input) != *self {
+                return;
+            }
+        }
+    }
+}
+
+/// Collects the library sources below `dir`. Solutions in `src/bin` are left out, only the watched day matters.
+fn collect_sources(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            if path != Path::new("src/bin") {
+                collect_sources(&path, files);
+            }
+        } else if path.extension().is_some_and(|x| x == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+/// Whether a file holds data of the day, e.g. `01.txt`, a numbered example like `01-2.txt`
+/// or the expected answers of an example like `01.answers`.
+fn is_data_file(path: &Path, puzzle: PuzzleId) -> bool {
+    let day = puzzle.day.to_string();
+
+    path.extension()
+        .is_some_and(|x| x == "txt" || x == "answers")
+        && path
+            .file_stem()
+            .and_then(|x| x.to_str())
+            .is_some_and(|stem| {
+                stem == day
+                    || stem
+                        .strip_prefix(&day)
+                        .and_then(|x| x.strip_prefix('-'))
+                        .is_some_and(|x| x.parse::<usize>().is_ok())
+            })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::is_data_file;
+    use crate::puzzle;
+
+    #[test]
+    fn matches_data_files_of_the_day() {
+        let puzzle = puzzle!(2023, 1);
+        assert!(is_data_file(Path::new("data/2023/inputs/01.txt"), puzzle));
+        assert!(is_data_file(
+            Path::new("data/2023/examples/01-2.txt"),
+            puzzle
+        ));
+        assert!(is_data_file(
+            Path::new("data/2023/examples/01.answers"),
+            puzzle
+        ));
+        assert!(is_data_file(
+            Path::new("data/2023/examples/01-2.answers"),
+            puzzle
+        ));
+        assert!(!is_data_file(Path::new("data/2023/examples/01.md"), puzzle));
+        assert!(!is_data_file(Path::new("data/2023/inputs/11.txt"), puzzle));
+        assert!(!is_data_file(
+            Path::new("data/2023/inputs/01-x.txt"),
+            puzzle
+        ));
+    }
+}
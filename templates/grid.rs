@@ -0,0 +1,54 @@
+//! PUZZLE_TITLE
+advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER);
+
+/// The puzzle input as rows of characters, indexed by `grid[y][x]`.
+type Grid = Vec<Vec<char>>;
+
+fn parse_grid(input: &str) -> Grid {
+    input.lines().map(|line| line.chars().collect()).collect()
+}
+
+/// The in-bounds neighbours of a cell, without diagonals.
+#[allow(dead_code)]
+fn neighbours(grid: &Grid, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+    [(0, -1), (1, 0), (0, 1), (-1, 0)]
+        .into_iter()
+        .filter_map(move |(dx, dy)| {
+            let x = x.checked_add_signed(dx)?;
+            let y = y.checked_add_signed(dy)?;
+            (y < grid.len() && x < grid[y].len()).then_some((x, y))
+        })
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let _grid = parse_grid(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let _grid = parse_grid(input);
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, PART_ONE_EXPECTED);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, PART_TWO_EXPECTED);
+    }
+
+    #[test]
+    fn test_examples() {
+        advent_of_code::template::examples::check(PUZZLE, 1, part_one);
+        advent_of_code::template::examples::check(PUZZLE, 2, part_two);
+    }
+}
@@ -0,0 +1,33 @@
+//! PUZZLE_TITLE
+advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER);
+
+pub fn part_one(input: &str) -> Option<u64> {
+    None
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, PART_ONE_EXPECTED);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, PART_TWO_EXPECTED);
+    }
+
+    #[test]
+    fn test_examples() {
+        advent_of_code::template::examples::check(PUZZLE, 1, part_one);
+        advent_of_code::template::examples::check(PUZZLE, 2, part_two);
+    }
+}
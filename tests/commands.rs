@@ -0,0 +1,526 @@
+/// End-to-end tests of the commands that talk to the Advent of Code website, run against a local mock server.
+/// Every test runs the binary in its own temporary directory, so the data folder of the repository is not touched.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command, Output},
+};
+
+use advent_of_code::puzzle;
+use advent_of_code::template::aoc_client::AocClient;
+use advent_of_code::template::submissions::Verdict;
+use common::mock_server::{MockServer, MOCK_SESSION};
+
+mod common;
+
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("aoc-test-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(path.join("src/bin")).unwrap();
+        Self(path)
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+
+    fn read(&self, file: &str) -> String {
+        fs::read_to_string(self.0.join(file)).unwrap()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn run(dir: &TempDir, server: &MockServer, session: &str, args: &[&str]) -> Output {
+    run_with_user(dir, server, session, None, args)
+}
+
+fn run_with_user(
+    dir: &TempDir,
+    server: &MockServer,
+    session: &str,
+    user_id: Option<&str>,
+    args: &[&str],
+) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_advent_of_code"));
+    command
+        .args(args)
+        .current_dir(dir.path())
+        .env("AOC_BASE_URL", server.url())
+        .env("AOC_SESSION", session)
+        .env("AOC_YEAR", "2023")
+        .env_remove("AOC_USER_ID");
+
+    if let Some(user_id) = user_id {
+        command.env("AOC_USER_ID", user_id);
+    }
+
+    command.output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into()
+}
+
+#[test]
+fn downloads_input_and_puzzle() {
+    let server = MockServer::start().unwrap();
+    let dir = TempDir::new("download");
+
+    let output = run(&dir, &server, MOCK_SESSION, &["download", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    assert_eq!(dir.read("data/2023/inputs/01.txt"), "input of day 1\n");
+    let puzzle = dir.read("data/2023/puzzles/01.md");
+    assert!(puzzle.starts_with("## --- Day 1: Mock Puzzle ---\n"));
+    assert!(puzzle.contains("```\n1\n2\n3\n```"));
+    assert!(puzzle.contains("`*6*`"));
+
+    let paths: Vec<String> = server.requests().into_iter().map(|x| x.path).collect();
+    assert_eq!(paths, vec!["/2023/day/1/input", "/2023/day/1"]);
+}
+
+#[test]
+fn download_fails_without_valid_session() {
+    let server = MockServer::start().unwrap();
+    let dir = TempDir::new("download-session");
+
+    let output = run(&dir, &server, "expired", &["download", "1"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("status 400: Puzzle inputs differ by user."));
+    assert!(!dir.path().join("data/2023/inputs/01.txt").exists());
+}
+
+#[test]
+fn reads_puzzle() {
+    let server = MockServer::start().unwrap();
+    let dir = TempDir::new("read");
+
+    let output = run(&dir, &server, MOCK_SESSION, &["read", "7"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("## --- Day 7: Mock Puzzle ---"));
+    assert_eq!(
+        dir.read("data/2023/puzzles/07.md"),
+        stdout(&output).trim_end().to_string() + "\n"
+    );
+}
+
+#[test]
+fn scaffolds_day() {
+    let server = MockServer::start().unwrap();
+    let dir = TempDir::new("scaffold");
+
+    let output = run(&dir, &server, MOCK_SESSION, &["scaffold", "3"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(dir
+        .read("src/bin/2023_03.rs")
+        .starts_with("advent_of_code::solution!(2023, 3);"));
+    assert_eq!(dir.read("data/2023/inputs/03.txt"), "");
+    assert_eq!(dir.read("data/2023/examples/03.txt"), "");
+
+    let output = run(&dir, &server, MOCK_SESSION, &["scaffold", "3"]);
+    assert!(!output.status.success());
+
+    let output = run(
+        &dir,
+        &server,
+        MOCK_SESSION,
+        &["scaffold", "--year", "2022", "3"],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(dir
+        .read("src/bin/2022_03.rs")
+        .starts_with("advent_of_code::solution!(2022, 3);"));
+    assert_eq!(dir.read("data/2022/inputs/03.txt"), "");
+}
+
+#[test]
+fn scaffolds_from_templates() {
+    let server = MockServer::start().unwrap();
+    let dir = TempDir::new("scaffold-templates");
+    fs::create_dir_all(dir.path().join("templates")).unwrap();
+    fs::write(
+        dir.path().join("templates/default.rs"),
+        "// YEAR_NUMBER DAY_NUMBER PUZZLE_TITLE\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("templates/big.rs"), "// big DAY_NUMBER\n").unwrap();
+
+    let output = run(&dir, &server, MOCK_SESSION, &["scaffold", "6"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(dir.read("src/bin/2023_06.rs"), "// 2023 6 Day 6\n");
+
+    run(&dir, &server, MOCK_SESSION, &["download", "7"]);
+    let output = run(
+        &dir,
+        &server,
+        MOCK_SESSION,
+        &["scaffold", "7", "--template", "big"],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(dir.read("src/bin/2023_07.rs"), "// big 7\n");
+    fs::remove_file(dir.path().join("src/bin/2023_07.rs")).unwrap();
+
+    let output = run(&dir, &server, MOCK_SESSION, &["scaffold", "7"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        dir.read("src/bin/2023_07.rs"),
+        "// 2023 7 Day 7: Mock Puzzle\n"
+    );
+
+    let output = run(
+        &dir,
+        &server,
+        MOCK_SESSION,
+        &["scaffold", "8", "--template", "nope"],
+    );
+    assert!(!output.status.success());
+    assert!(!dir.path().join("src/bin/2023_08.rs").exists());
+}
+
+#[test]
+fn scaffolds_example_slots() {
+    let server = MockServer::start().unwrap();
+    let dir = TempDir::new("scaffold-examples");
+
+    let output = run(
+        &dir,
+        &server,
+        MOCK_SESSION,
+        &["scaffold", "5", "--examples", "3"],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    for path in ["05.txt", "05-2.txt", "05-3.txt"] {
+        assert_eq!(dir.read(&format!("data/2023/examples/{path}")), "");
+    }
+    assert!(!dir.path().join("data/2023/examples/05-4.txt").exists());
+    assert!(dir
+        .read("src/bin/2023_05.rs")
+        .contains("advent_of_code::template::examples::check(PUZZLE, 2, part_two);"));
+}
+
+#[test]
+fn extracts_examples_for_scaffold() {
+    let server = MockServer::start().unwrap();
+    let dir = TempDir::new("examples");
+
+    let output = run(&dir, &server, MOCK_SESSION, &["examples", "4"]);
+    assert!(!output.status.success());
+
+    run(&dir, &server, MOCK_SESSION, &["download", "4"]);
+    let output = run(&dir, &server, MOCK_SESSION, &["examples", "4"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(dir.read("data/2023/examples/04.txt"), "1\n2\n3\n");
+    assert_eq!(dir.read("data/2023/examples/04.answers"), "part_1: 6\n");
+
+    let output = run(&dir, &server, MOCK_SESSION, &["scaffold", "4"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let module = dir.read("src/bin/2023_04.rs");
+    assert!(module.contains("assert_eq!(result, Some(6));"));
+    assert!(module.contains("assert_eq!(result, None);"));
+    assert_eq!(dir.read("data/2023/examples/04.txt"), "1\n2\n3\n");
+}
+
+#[test]
+fn starts_day() {
+    let server = MockServer::start().unwrap();
+    let dir = TempDir::new("start");
+
+    let output = run(&dir, &server, MOCK_SESSION, &["start", "9"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("## --- Day 9: Mock Puzzle ---"));
+    assert_eq!(dir.read("data/2023/inputs/09.txt"), "input of day 9\n");
+    assert_eq!(dir.read("data/2023/examples/09.txt"), "1\n2\n3\n");
+    let module = dir.read("src/bin/2023_09.rs");
+    assert!(module.contains("assert_eq!(result, Some(6));"));
+
+    fs::write(dir.path().join("src/bin/2023_09.rs"), "// solved\n").unwrap();
+    fs::write(dir.path().join("data/2023/examples/09.txt"), "4\n").unwrap();
+
+    let output = run(&dir, &server, MOCK_SESSION, &["start", "9"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(dir.read("src/bin/2023_09.rs"), "// solved\n");
+    assert_eq!(dir.read("data/2023/examples/09.txt"), "4\n");
+
+    // once everything is set up, nothing is downloaded and no session is needed.
+    let puzzle = format!(
+        "{}\n## --- Part Two ---\n",
+        dir.read("data/2023/puzzles/09.md")
+    );
+    fs::write(dir.path().join("data/2023/puzzles/09.md"), puzzle).unwrap();
+    let requests = server.requests().len();
+
+    let output = run(&dir, &server, "", &["start", "9"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(server.requests().len(), requests);
+
+    let inputs = server
+        .requests()
+        .into_iter()
+        .filter(|x| x.path == "/2023/day/9/input")
+        .count();
+    assert_eq!(inputs, 1);
+}
+
+#[test]
+fn updates_readme_stars() {
+    let server = MockServer::start().unwrap();
+    server.set_leaderboard(
+        3031,
+        r#"{"event": "2023", "owner_id": 3031, "members": {"3031": {"id": 3031, "name": "Ferris", "stars": 3, "local_score": 10,
+            "completion_day_level": {"1": {"1": {"get_star_ts": 1701406800}, "2": {"get_star_ts": 1701407400}}, "2": {"1": {"get_star_ts": 1701493200}}}}}}"#,
+    );
+    let dir = TempDir::new("readme");
+    let marker = "<!--- advent_readme_stars table --->";
+    fs::write(
+        dir.path().join("README.md"),
+        format!("# readme\n{marker}\n{marker}\n"),
+    )
+    .unwrap();
+
+    let output = run(&dir, &server, MOCK_SESSION, &["readme"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("AOC_USER_ID"));
+
+    let output = run_with_user(&dir, &server, MOCK_SESSION, Some("3031"), &["readme"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let readme = dir.read("README.md");
+    assert!(readme.contains("## 2023 Results"));
+    assert!(readme.contains("| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |"));
+    assert!(readme.contains("| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   |"));
+
+    let paths: Vec<String> = server.requests().into_iter().map(|x| x.path).collect();
+    assert_eq!(paths, vec!["/2023/leaderboard/private/view/3031.json"]);
+}
+
+#[test]
+fn shows_leaderboard() {
+    let server = MockServer::start().unwrap();
+    server.set_leaderboard(
+        3031,
+        r#"{"event": "2023", "owner_id": 3031, "members": {
+            "3031": {"id": 3031, "name": "Ferris", "stars": 3, "local_score": 10,
+                "completion_day_level": {"1": {"1": {"get_star_ts": 1701406830}, "2": {"get_star_ts": 1701410462}}, "2": {"1": {"get_star_ts": 1701493200}}}},
+            "42": {"id": 42, "name": null, "stars": 1, "local_score": 2,
+                "completion_day_level": {"2": {"1": {"get_star_ts": 1701500000}}}}}}"#,
+    );
+    let dir = TempDir::new("leaderboard");
+
+    let args = ["leaderboard", "--day", "1"];
+    let output = run_with_user(&dir, &server, MOCK_SESSION, Some("3031"), &args);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let out = stdout(&output);
+    assert!(out.contains("private leaderboard of 2 members"));
+    assert!(out.contains("(anonymous user #42)"));
+    assert!(out.contains("Day 1 · time after unlock"));
+    assert!(out.contains("00:00:30"));
+    assert!(out.contains("01:01:02"));
+
+    // the downloaded leaderboard is reused for 15 minutes.
+    let output = run_with_user(&dir, &server, MOCK_SESSION, Some("3031"), &["leaderboard"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Day 2 · time after unlock"));
+    assert!(dir.path().join("data/2023/leaderboard.json").exists());
+
+    let paths: Vec<String> = server.requests().into_iter().map(|x| x.path).collect();
+    assert_eq!(paths, vec!["/2023/leaderboard/private/view/3031.json"]);
+}
+
+#[test]
+fn prints_status() {
+    let server = MockServer::start().unwrap();
+    let dir = TempDir::new("status");
+    for (path, contents) in [
+        ("src/bin/2023_01.rs", ""),
+        ("data/2023/inputs/01.txt", "input of day 1\n"),
+        ("data/2023/inputs/02.txt", ""),
+        ("data/2023/examples/01.txt", "example\n"),
+        ("data/2023/examples/01-2.txt", "example\n"),
+        ("data/2023/answers/01.txt", "part_1: 42\n"),
+        (
+            "data/2023/submissions/01.jsonl",
+            "{\"part\":2,\"answer\":\"7\",\"verdict\":\"too_low\"}\n",
+        ),
+    ] {
+        let path = dir.path().join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    let output = run(&dir, &server, MOCK_SESSION, &["status", "--no-tests"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let out = stdout(&output);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 28);
+    assert!(lines[1].contains("2         -      ⭐"));
+    assert!(lines[1].contains("✖ 1"));
+    assert_eq!(lines[2], "  2  -     -      -         -      -       -");
+    assert!(lines[27].contains("1/50 ⭐"));
+    assert!(lines[27].contains("1/25 days scaffolded"));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+#[cfg_attr(not(feature = "solutions"), ignore = "needs the `solutions` feature")]
+fn applies_timeouts_to_single_executions() {
+    let server = MockServer::start().unwrap();
+    let dir = TempDir::new("timeout");
+    fs::create_dir_all(dir.path().join("data/2023/inputs")).unwrap();
+    fs::write(
+        dir.path().join("data/2023/inputs/01.txt"),
+        "1abc2\n".repeat(1000),
+    )
+    .unwrap();
+
+    // benchmarking takes longer than the timeout, but every single execution is well below it.
+    let output = run(
+        &dir,
+        &server,
+        MOCK_SESSION,
+        &[
+            "all",
+            "--time",
+            "--min-samples",
+            "300",
+            "--timeout",
+            "200ms",
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let stdout = stdout(&output);
+    let day = stdout
+        .split("Day 02")
+        .next()
+        .and_then(|x| x.split("Day 01").nth(1))
+        .unwrap();
+    assert!(day.contains("Part 1: \x1b[1m12000\x1b[0m"), "{day}");
+    assert!(day.contains("samples"), "{day}");
+    assert!(!day.contains("timeout"), "{day}");
+}
+
+#[test]
+#[cfg_attr(not(feature = "solutions"), ignore = "needs the `solutions` feature")]
+fn writes_requested_reports_without_release() {
+    let server = MockServer::start().unwrap();
+    let dir = TempDir::new("report");
+    fs::create_dir_all(dir.path().join("data/2023/inputs")).unwrap();
+    fs::write(dir.path().join("data/2023/inputs/01.txt"), "1abc2\n").unwrap();
+
+    let output = run(
+        &dir,
+        &server,
+        MOCK_SESSION,
+        &["all", "--time", "--report", "csv:bench.csv"],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Successfully wrote csv report"));
+
+    let csv = dir.read("bench.csv");
+    assert!(
+        csv.lines().any(|line| line.starts_with("2023,1,1,")),
+        "{csv}"
+    );
+}
+
+#[test]
+#[cfg_attr(not(feature = "solutions"), ignore = "needs the `solutions` feature")]
+fn rejects_comparisons_without_release() {
+    let server = MockServer::start().unwrap();
+    let dir = TempDir::new("compare");
+
+    let output = run(&dir, &server, MOCK_SESSION, &["all", "--time", "--compare"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("`--compare` requires release timings"));
+    assert!(!stdout(&output).contains("Day 01"));
+}
+
+#[test]
+fn submits_answers() {
+    let server = MockServer::start().unwrap();
+    server.set_answer(2, 2, "100");
+    let client = AocClient::new(&server.url(), MOCK_SESSION);
+    let puzzle = puzzle!(2022, 2);
+
+    let submit = |part: u8, answer: &str| client.submit(puzzle, part, answer).unwrap().0;
+
+    assert_eq!(submit(1, "42"), Verdict::Correct);
+    assert_eq!(submit(1, "abc"), Verdict::Wrong);
+    assert_eq!(submit(2, "101"), Verdict::TooHigh);
+    assert_eq!(submit(2, "99"), Verdict::TooLow);
+    assert_eq!(submit(2, "100"), Verdict::Correct);
+
+    server.set_rate_limited(true);
+    assert_eq!(submit(2, "100"), Verdict::RateLimited);
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.path, "/2022/day/2/answer");
+    assert_eq!(request.body, "level=1&answer=42");
+}
+
+#[test]
+fn submits_solutions_once() {
+    let server = MockServer::start().unwrap();
+    server.set_answer(1, 1, "12");
+    let dir = TempDir::new("solve-submit");
+    fs::create_dir_all(dir.path().join("data/2023/inputs")).unwrap();
+    fs::write(dir.path().join("data/2023/inputs/01.txt"), "1abc2\n").unwrap();
+
+    // `cargo solve 1 --submit 1` runs the solution binary with these arguments.
+    let submit = || {
+        Command::new(env!("CARGO_BIN_EXE_2023_01"))
+            .args(["--submit", "1"])
+            .current_dir(dir.path())
+            .env("AOC_BASE_URL", server.url())
+            .env("AOC_SESSION", MOCK_SESSION)
+            .output()
+            .unwrap()
+    };
+
+    let output = submit();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("That's the right answer!"));
+    assert_eq!(dir.read("data/2023/answers/01.txt"), "part_1: 12\n");
+    assert_eq!(
+        dir.read("data/2023/submissions/01.jsonl").lines().count(),
+        1
+    );
+
+    let output = submit();
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("Refusing to submit 12"));
+
+    let posts = server
+        .requests()
+        .iter()
+        .filter(|request| request.method == "POST")
+        .count();
+    assert_eq!(posts, 1);
+}
+
+#[test]
+fn refuses_submissions_without_valid_session() {
+    let server = MockServer::start().unwrap();
+    let client = AocClient::new(&server.url(), "expired");
+
+    let error = client.submit(puzzle!(2023, 1), 1, "42").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "server responded with status 400: Please log in."
+    );
+}
@@ -0,0 +1,298 @@
+/// Local stand-in for the Advent of Code website, used to test the client and the commands offline.
+/// The server listens on a random local port and serves canned inputs, puzzle pages and answer responses.
+/// Point the client to it by setting `AOC_BASE_URL` to [`MockServer::url`] and `AOC_SESSION` to [`MOCK_SESSION`].
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+/// The only session token accepted by the server.
+pub const MOCK_SESSION: &str = "mock-session";
+
+/// The correct answer of parts without an answer set through [`MockServer::set_answer`].
+pub const DEFAULT_ANSWER: &str = "42";
+
+/// A request received by the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+#[derive(Default)]
+struct State {
+    answers: HashMap<(u8, u8), String>,
+    leaderboards: HashMap<String, String>,
+    rate_limited: bool,
+    requests: Vec<Request>,
+}
+
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl MockServer {
+    /// Starts the server on a background thread. It is stopped when dropped.
+    pub fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State::default()));
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let server_state = state.clone();
+        let server_stopped = stopped.clone();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if server_stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    // a failing connection should not bring down the server.
+                    let _ = handle_connection(stream, &server_state);
+                }
+            }
+        });
+
+        Ok(Self {
+            addr,
+            state,
+            stopped,
+        })
+    }
+
+    /// The base url of the server.
+    #[must_use]
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Sets the correct answer of a part.
+    pub fn set_answer(&self, day: u8, part: u8, answer: &str) {
+        self.lock().answers.insert((day, part), answer.into());
+    }
+
+    /// Sets the JSON served for the private leaderboard of the user `id`.
+    pub fn set_leaderboard(&self, id: u64, json: &str) {
+        self.lock().leaderboards.insert(id.to_string(), json.into());
+    }
+
+    /// While rate limited, every submission is answered with "you have to wait".
+    pub fn set_rate_limited(&self, rate_limited: bool) {
+        self.lock().rate_limited = rate_limited;
+    }
+
+    /// All requests received so far.
+    #[must_use]
+    pub fn requests(&self) -> Vec<Request> {
+        self.lock().requests.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        // wake up the accept loop, so the server thread can exit.
+        let _ = TcpStream::connect(self.addr);
+    }
+}
+
+fn handle_connection(stream: TcpStream, state: &Mutex<State>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut request_line = request_line.split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or_default().to_string();
+
+    let mut headers: HashMap<String, String> = HashMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            headers.insert(key.trim().to_ascii_lowercase(), value.trim().into());
+        }
+    }
+
+    let content_length = headers
+        .get("content-length")
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let request = Request {
+        method,
+        path,
+        body: String::from_utf8_lossy(&body).into(),
+    };
+
+    let authorized = headers.get("cookie").is_some_and(|cookie| {
+        cookie
+            .split(';')
+            .any(|x| x.trim() == format!("session={MOCK_SESSION}"))
+    });
+
+    let (status, body) = {
+        let mut state = state.lock().unwrap();
+        let response = respond(&request, authorized, &state);
+        state.requests.push(request);
+        response
+    };
+
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        _ => "Not Found",
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+fn respond(request: &Request, authorized: bool, state: &State) -> (u16, String) {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+
+    let (year, day, action) = match segments.as_slice() {
+        [_, "leaderboard", "private", "view", file] if request.method == "GET" => {
+            let leaderboard = file
+                .strip_suffix(".json")
+                .and_then(|id| state.leaderboards.get(id));
+            return match leaderboard {
+                Some(json) if authorized => (200, json.clone()),
+                Some(_) => (400, "Please log in.\n".into()),
+                None => not_found(),
+            };
+        }
+        [year, "day", day] => (*year, *day, None),
+        [year, "day", day, action] => (*year, *day, Some(*action)),
+        _ => return not_found(),
+    };
+
+    let Ok(day) = day.parse::<u8>() else {
+        return not_found();
+    };
+
+    match (request.method.as_str(), action) {
+        ("GET", None) => (200, puzzle_page(year, day)),
+        ("GET", Some("input")) if authorized => (200, format!("input of day {day}\n")),
+        ("GET", Some("input")) => (
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".into(),
+        ),
+        ("POST", Some("answer")) if authorized => {
+            let form = parse_form(&request.body);
+            let part = form.get("level").and_then(|x| x.parse().ok()).unwrap_or(1);
+            let answer = form.get("answer").map_or("", String::as_str);
+            let expected = state
+                .answers
+                .get(&(day, part))
+                .map_or(DEFAULT_ANSWER, String::as_str);
+            (
+                200,
+                answer_page(year, day, answer, expected, state.rate_limited),
+            )
+        }
+        ("POST", Some("answer")) => (400, "Please log in.\n".into()),
+        _ => not_found(),
+    }
+}
+
+fn not_found() -> (u16, String) {
+    (404, "404 Not Found\n".into())
+}
+
+fn puzzle_page(year: &str, day: u8) -> String {
+    page(
+        &format!("Day {day} - Advent of Code {year}"),
+        &format!(
+            "<article class=\"day-desc\"><h2>--- Day {day}: Mock Puzzle ---</h2>\n\
+            <p>The elves need you to add up a list of numbers. For example:</p>\n\
+            <pre><code>1\n2\n3\n</code></pre>\n\
+            <p>In this example, the sum of these numbers is <code><em>6</em></code>.</p>\n\
+            <p>What is the <em>sum</em> of all numbers in your list?</p>\n\
+            </article>"
+        ),
+    )
+}
+
+fn answer_page(year: &str, day: u8, answer: &str, expected: &str, rate_limited: bool) -> String {
+    let back = format!("<a href=\"/{year}/day/{day}\">[Return to Day {day}]</a>");
+
+    let message = if rate_limited {
+        format!("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 30s left to wait. {back}")
+    } else if answer == expected {
+        format!("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to saving Christmas. {back}")
+    } else {
+        let hint = match (answer.parse::<i128>(), expected.parse::<i128>()) {
+            (Ok(answer), Ok(expected)) if answer > expected => "; your answer is too high",
+            (Ok(answer), Ok(expected)) if answer < expected => "; your answer is too low",
+            _ => "",
+        };
+        format!("That's not the right answer{hint}.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. {back}")
+    };
+
+    page(
+        &format!("Day {day} - Advent of Code {year}"),
+        &format!("<article><p>{message}</p></article>"),
+    )
+}
+
+fn page(title: &str, main: &str) -> String {
+    format!("<!DOCTYPE html>\n<html lang=\"en-us\">\n<head>\n<title>{title}</title>\n</head>\n<body>\n<main>\n{main}\n</main>\n</body>\n</html>\n")
+}
+
+fn parse_form(body: &str) -> HashMap<String, String> {
+    body.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (url_decode(key), url_decode(value)))
+        .collect()
+}
+
+fn url_decode(s: &str) -> String {
+    let mut bytes = vec![];
+    let mut iter = s.bytes();
+
+    while let Some(b) = iter.next() {
+        match b {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex: Vec<u8> = iter.by_ref().take(2).collect();
+                match std::str::from_utf8(&hex)
+                    .ok()
+                    .and_then(|x| u8::from_str_radix(x, 16).ok())
+                {
+                    Some(decoded) => bytes.push(decoded),
+                    None => {
+                        bytes.push(b'%');
+                        bytes.extend(hex);
+                    }
+                }
+            }
+            _ => bytes.push(b),
+        }
+    }
+
+    String::from_utf8_lossy(&bytes).into()
+}